@@ -0,0 +1,242 @@
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+use crate::errors::ContractError;
+use crate::group;
+use crate::storage;
+use crate::types::{GroupStatus, GroupVisibility, SavingsGroup};
+
+/// Choose who may join a forming group.
+pub fn set_visibility(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    visibility: GroupVisibility,
+) -> Result<(), ContractError> {
+    let mut group = forming_group_of(env, &admin, group_id)?;
+
+    group.visibility = visibility.clone();
+    storage::set_group(env, &group);
+
+    env.events()
+        .publish((crate::symbol_short!("grp_vis"),), (group_id, visibility));
+
+    Ok(())
+}
+
+/// Ask to join a group. The group's collateral is held with the request, so the
+/// admin can admit it without the member signing again, and returned if the
+/// request is withdrawn, rejected or the group stops forming first.
+pub fn request_join(env: &Env, member: Address, group_id: u64) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    if group.members.contains(&member) {
+        return Err(ContractError::AlreadyMember);
+    }
+
+    let mut requests = storage::get_join_requests(env, group_id);
+    if requests.contains(&member) {
+        return Err(ContractError::AlreadyRequested);
+    }
+    requests.push_back(member.clone());
+    storage::set_join_requests(env, group_id, &requests);
+    group::lock_collateral(env, &group, &member);
+
+    env.events()
+        .publish((crate::symbol_short!("join_req"),), (group_id, member));
+
+    Ok(())
+}
+
+/// Withdraw a pending join request and take its collateral back.
+pub fn cancel_join_request(env: &Env, member: Address, group_id: u64) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    let mut requests = storage::get_join_requests(env, group_id);
+    let i = requests
+        .first_index_of(&member)
+        .ok_or(ContractError::NoJoinRequest)?;
+    requests.remove(i);
+    storage::set_join_requests(env, group_id, &requests);
+    group::release_collateral(env, &group, &member);
+
+    env.events()
+        .publish((crate::symbol_short!("join_cncl"),), (group_id, member));
+
+    Ok(())
+}
+
+/// Let an address into the group. A pending join request is admitted straight
+/// away; otherwise the address is allowlisted so it can join whatever the
+/// group's visibility.
+pub fn approve_join(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    member: Address,
+) -> Result<(), ContractError> {
+    let mut group = forming_group_of(env, &admin, group_id)?;
+
+    if storage::get_join_requests(env, group_id).contains(&member) {
+        group::admit_member(env, &mut group, member.clone())?;
+    } else {
+        let mut allowlist = storage::get_allowlist(env, group_id);
+        if !allowlist.contains(&member) {
+            allowlist.push_back(member.clone());
+            storage::set_allowlist(env, group_id, &allowlist);
+        }
+    }
+
+    env.events()
+        .publish((crate::symbol_short!("join_ok"),), (group_id, member));
+
+    Ok(())
+}
+
+/// Turn down a pending join request and return its collateral.
+pub fn reject_join(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    member: Address,
+) -> Result<(), ContractError> {
+    let group = forming_group_of(env, &admin, group_id)?;
+
+    let mut requests = storage::get_join_requests(env, group_id);
+    let i = requests
+        .first_index_of(&member)
+        .ok_or(ContractError::NoJoinRequest)?;
+    requests.remove(i);
+    storage::set_join_requests(env, group_id, &requests);
+    group::release_collateral(env, &group, &member);
+
+    env.events()
+        .publish((crate::symbol_short!("join_rej"),), (group_id, member));
+
+    Ok(())
+}
+
+/// Turn down every pending join request of a group that is no longer forming,
+/// returning their collateral.
+pub fn drop_join_requests(env: &Env, group: &SavingsGroup) {
+    for member in storage::get_join_requests(env, group.id).iter() {
+        group::release_collateral(env, group, &member);
+
+        env.events()
+            .publish((crate::symbol_short!("join_rej"),), (group.id, member));
+    }
+    storage::remove_join_requests(env, group.id);
+}
+
+pub fn get_join_requests(env: &Env, group_id: u64) -> Result<Vec<Address>, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_join_requests(env, group_id))
+}
+
+/// Take an address off a group's allowlist.
+pub fn revoke_join(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    member: Address,
+) -> Result<(), ContractError> {
+    forming_group_of(env, &admin, group_id)?;
+
+    let mut allowlist = storage::get_allowlist(env, group_id);
+    let i = allowlist
+        .first_index_of(&member)
+        .ok_or(ContractError::NotInvited)?;
+    allowlist.remove(i);
+    storage::set_allowlist(env, group_id, &allowlist);
+
+    env.events()
+        .publish((crate::symbol_short!("join_rvk"),), (group_id, member));
+
+    Ok(())
+}
+
+/// Publish the SHA-256 hash of an invite code. The code itself is shared off-chain.
+pub fn set_invite_code(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    code_hash: BytesN<32>,
+) -> Result<(), ContractError> {
+    forming_group_of(env, &admin, group_id)?;
+
+    storage::set_invite_code(env, group_id, &code_hash);
+
+    env.events()
+        .publish((crate::symbol_short!("inv_code"),), group_id);
+
+    Ok(())
+}
+
+/// Join an invite-code group by presenting the code.
+pub fn join_group_with_code(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    code: Bytes,
+) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.visibility != GroupVisibility::InviteCode {
+        return Err(ContractError::NotInvited);
+    }
+
+    let hash: BytesN<32> = env.crypto().sha256(&code).to_bytes();
+    if storage::get_invite_code(env, group_id) != Some(hash) {
+        return Err(ContractError::InvalidInviteCode);
+    }
+
+    group::admit_member(env, &mut group, member)
+}
+
+pub fn get_allowlist(env: &Env, group_id: u64) -> Result<Vec<Address>, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_allowlist(env, group_id))
+}
+
+/// Check that the group's visibility lets `member` join directly.
+pub fn check_can_join(
+    env: &Env,
+    group: &SavingsGroup,
+    member: &Address,
+) -> Result<(), ContractError> {
+    if group.visibility == GroupVisibility::Public
+        || storage::get_allowlist(env, group.id).contains(member)
+    {
+        return Ok(());
+    }
+    Err(ContractError::NotInvited)
+}
+
+fn forming_group_of(
+    env: &Env,
+    admin: &Address,
+    group_id: u64,
+) -> Result<SavingsGroup, ContractError> {
+    admin.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if *admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    Ok(group)
+}
@@ -1,8 +1,18 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, Env, Map, String, Vec};
 
+use crate::access;
+use crate::asset;
+use crate::contribution;
 use crate::errors::ContractError;
+use crate::exit;
+use crate::group;
+use crate::keeper;
+use crate::payout;
 use crate::storage;
-use crate::types::{Dispute, GroupStatus};
+use crate::types::{Dispute, DisputeOutcome, DisputeStatus, GroupStatus, SavingsGroup};
+
+/// How long members have to vote on a dispute.
+pub const DISPUTE_VOTING_PERIOD: u64 = 3 * 24 * 60 * 60;
 
 pub fn pause_group(env: &Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
     admin.require_auth();
@@ -13,8 +23,9 @@ pub fn pause_group(env: &Env, admin: Address, group_id: u64) -> Result<(), Contr
         return Err(ContractError::Unauthorized);
     }
 
-    if group.status == GroupStatus::Completed {
-        return Err(ContractError::GroupCompleted);
+    group::ensure_open(&group)?;
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
     }
 
     group.status = GroupStatus::Paused;
@@ -53,6 +64,7 @@ pub fn raise_dispute(
     member: Address,
     group_id: u64,
     reason: String,
+    proposed: DisputeOutcome,
 ) -> Result<(), ContractError> {
     member.require_auth();
 
@@ -74,28 +86,128 @@ pub fn raise_dispute(
         return Err(ContractError::GroupNotActive);
     }
 
+    check_outcome(&group, &proposed)?;
+
+    let dispute_id = storage::get_dispute_counter(env, group_id) + 1;
+    storage::set_dispute_counter(env, group_id, dispute_id);
+
     let dispute = Dispute {
+        id: dispute_id,
         raised_by: member.clone(),
         reason,
         raised_at: env.ledger().timestamp(),
+        proposed,
+        votes: Map::new(env),
+        voting_ends: env.ledger().timestamp() + DISPUTE_VOTING_PERIOD,
+        evidence: Vec::new(env),
+        status: DisputeStatus::Open,
+        resolved_at: 0,
     };
 
     group.status = GroupStatus::Disputed;
     storage::set_group(env, &group);
     storage::set_dispute(env, group_id, &dispute);
 
+    env.events().publish(
+        (crate::symbol_short!("dispute"),),
+        (group_id, dispute_id, member),
+    );
+
+    Ok(())
+}
+
+/// Cast a member's vote on the open dispute. The outcome is executed as soon as
+/// it is backed by a majority of all members.
+pub fn vote_dispute(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    outcome: DisputeOutcome,
+) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Disputed {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    let mut dispute = open_dispute(env, group_id)?;
+
+    if env.ledger().timestamp() > dispute.voting_ends {
+        return Err(ContractError::VotingClosed);
+    }
+
+    if dispute.votes.contains_key(member.clone()) {
+        return Err(ContractError::AlreadyVoted);
+    }
+
+    check_outcome(&group, &outcome)?;
+
+    dispute.votes.set(member.clone(), outcome.clone());
+    storage::set_dispute(env, group_id, &dispute);
+
     env.events()
-        .publish((crate::symbol_short!("dispute"),), (group_id, member));
+        .publish((crate::symbol_short!("dsp_vote"),), (group_id, member));
+
+    if count_votes(&dispute, &outcome) * 2 > group.members.len() {
+        execute_outcome(env, &mut group, dispute, outcome);
+    }
 
     Ok(())
 }
 
+/// Close an expired vote. Without a quorum of more than half the members, or with
+/// a tie for first place, the dispute is dismissed.
+pub fn finalize_dispute(env: &Env, group_id: u64) -> Result<(), ContractError> {
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Disputed {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    let dispute = open_dispute(env, group_id)?;
+
+    if env.ledger().timestamp() <= dispute.voting_ends {
+        return Err(ContractError::VotingOpen);
+    }
+
+    let mut outcome = DisputeOutcome::Dismiss;
+    if dispute.votes.len() * 2 > group.members.len() {
+        let mut best = 0;
+        let mut tied = false;
+        for candidate in dispute.votes.values() {
+            let count = count_votes(&dispute, &candidate);
+            if count > best {
+                best = count;
+                outcome = candidate;
+                tied = false;
+            } else if count == best && candidate != outcome {
+                tied = true;
+            }
+        }
+        if tied {
+            outcome = DisputeOutcome::Dismiss;
+        }
+    }
+
+    execute_outcome(env, &mut group, dispute, outcome);
+
+    Ok(())
+}
+
+/// Dismiss a dispute without a vote. Reserved for the protocol admin, since the
+/// dispute may concern the group admin.
 pub fn resolve_dispute(env: &Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
     admin.require_auth();
 
     let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
 
-    if admin != group.admin && admin != storage::get_admin(env) {
+    if admin != storage::get_admin(env) {
         return Err(ContractError::Unauthorized);
     }
 
@@ -103,54 +215,250 @@ pub fn resolve_dispute(env: &Env, admin: Address, group_id: u64) -> Result<(), C
         return Err(ContractError::GroupNotActive);
     }
 
-    group.status = GroupStatus::Active;
-    storage::set_group(env, &group);
-    storage::remove_dispute(env, group_id);
+    let dispute = open_dispute(env, group_id)?;
+    execute_outcome(env, &mut group, dispute, DisputeOutcome::Dismiss);
 
-    env.events()
-        .publish((crate::symbol_short!("resolved"),), group_id);
+    Ok(())
+}
 
+/// Attach a piece of evidence (a content hash or URI) to an unresolved dispute.
+pub fn add_evidence(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    dispute_id: u32,
+    evidence: String,
+) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    let mut dispute =
+        storage::get_dispute(env, group_id, dispute_id).ok_or(ContractError::DisputeNotFound)?;
+
+    if dispute.status != DisputeStatus::Open {
+        return Err(ContractError::DisputeResolved);
+    }
+
+    dispute.evidence.push_back(evidence);
+    storage::set_dispute(env, group_id, &dispute);
+
+    env.events().publish(
+        (crate::symbol_short!("evidence"),),
+        (group_id, dispute_id, member),
+    );
+
+    Ok(())
+}
+
+pub fn get_dispute(env: &Env, group_id: u64, dispute_id: u32) -> Result<Dispute, ContractError> {
+    storage::get_dispute(env, group_id, dispute_id).ok_or(ContractError::DisputeNotFound)
+}
+
+pub fn list_disputes(env: &Env, group_id: u64) -> Result<Vec<Dispute>, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    let mut disputes = Vec::new(env);
+    for dispute_id in 1..=storage::get_dispute_counter(env, group_id) {
+        if let Some(dispute) = storage::get_dispute(env, group_id, dispute_id) {
+            disputes.push_back(dispute);
+        }
+    }
+    Ok(disputes)
+}
+
+/// The dispute currently freezing a group is always the latest one raised.
+fn open_dispute(env: &Env, group_id: u64) -> Result<Dispute, ContractError> {
+    let dispute_id = storage::get_dispute_counter(env, group_id);
+    storage::get_dispute(env, group_id, dispute_id).ok_or(ContractError::DisputeNotFound)
+}
+
+fn check_outcome(group: &SavingsGroup, outcome: &DisputeOutcome) -> Result<(), ContractError> {
+    match outcome {
+        DisputeOutcome::RemoveMember(m) | DisputeOutcome::ReplaceAdmin(m) => {
+            if !group.members.contains(m) {
+                return Err(ContractError::NotMember);
+            }
+        }
+        DisputeOutcome::Dismiss | DisputeOutcome::DissolveGroup => {}
+    }
     Ok(())
 }
 
+fn count_votes(dispute: &Dispute, outcome: &DisputeOutcome) -> u32 {
+    let mut count = 0;
+    for vote in dispute.votes.values() {
+        if vote == *outcome {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn execute_outcome(
+    env: &Env,
+    group: &mut SavingsGroup,
+    mut dispute: Dispute,
+    outcome: DisputeOutcome,
+) {
+    group.status = GroupStatus::Active;
+    match &outcome {
+        DisputeOutcome::Dismiss => {}
+        DisputeOutcome::RemoveMember(member) => {
+            group::remove_active_member(env, group, member);
+        }
+        DisputeOutcome::DissolveGroup => unwind_group(env, group),
+        DisputeOutcome::ReplaceAdmin(new_admin) => {
+            group.admin = new_admin.clone();
+            storage::remove_pending_group_admin(env, group.id);
+        }
+    }
+    storage::set_group(env, group);
+
+    dispute.status = DisputeStatus::Resolved(outcome.clone());
+    dispute.resolved_at = env.ledger().timestamp();
+    storage::set_dispute(env, group.id, &dispute);
+
+    env.events().publish(
+        (crate::symbol_short!("resolved"),),
+        (group.id, dispute.id, outcome),
+    );
+
+    payout::auto_payout(env, group.id);
+}
+
 pub fn emergency_withdraw(env: &Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
     admin.require_auth();
 
-    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
 
     // Only protocol admin can trigger emergency withdraw
     if admin != storage::get_admin(env) {
         return Err(ContractError::Unauthorized);
     }
 
-    if group.status == GroupStatus::Completed {
-        return Err(ContractError::GroupCompleted);
+    group::ensure_open(&group)?;
+
+    unwind_group(env, &mut group);
+
+    env.events()
+        .publish((crate::symbol_short!("emergenc"),), group_id);
+
+    Ok(())
+}
+
+/// Return every member's stake in an unfinished group and mark it completed.
+pub fn unwind_group(env: &Env, group: &mut SavingsGroup) {
+    let group_id = group.id;
+    let round_info = storage::get_round(env, group_id, group.current_round);
+
+    let mut tokens = Vec::new(env);
+    tokens.push_back(group.token.clone());
+    for token in storage::get_accepted_tokens(env, group_id).keys() {
+        tokens.push_back(token);
     }
 
-    // Calculate remaining balance and distribute equally
-    let token_client = soroban_sdk::token::Client::new(env, &group.token);
-    let contract_addr = env.current_contract_address();
-    let balance = token_client.balance(&contract_addr);
+    for token in tokens.iter() {
+        // Refund each member exactly what they paid into the unfinished round,
+        // in the token they paid with. Earlier rounds have already been paid out.
+        let mut refunds: Map<Address, i128> = Map::new(env);
+        if let Some(round_info) = &round_info {
+            for (member, amount) in round_info.payments.iter() {
+                if asset::contribution_token(env, group, &member) == token {
+                    refunds.set(member, amount);
+                }
+            }
+        }
 
-    if balance > 0 {
-        let per_member = balance / group.members.len() as i128;
-        if per_member > 0 {
-            for member in group.members.iter() {
-                token_client.transfer(&contract_addr, &member, &per_member);
+        // Position bids placed while forming go back to their bidders, and exited
+        // members are repaid as far as the escrow allows
+        if token == group.token {
+            for (member, bid) in storage::get_position_bids(env, group_id).iter() {
+                refunds.set(member.clone(), refunds.get(member).unwrap_or(0) + bid);
+            }
+
+            let mut available = asset::escrow_balance(env, group, &token);
+            for amount in refunds.values() {
+                available -= amount;
+            }
+            for (claimant, claim) in exit::take_claims(env, group_id).iter() {
+                let amount = claim.min(available);
+                if amount > 0 {
+                    refunds.set(
+                        claimant.clone(),
+                        refunds.get(claimant).unwrap_or(0) + amount,
+                    );
+                    available -= amount;
+                }
             }
         }
+
+        refund_token(env, group, &token, &refunds);
+    }
+    storage::remove_position_bids(env, group_id);
+    storage::remove_bid_pool(env, group_id);
+    access::drop_join_requests(env, group);
+
+    for member in group.members.iter() {
+        group::release_collateral(env, group, &member);
+        contribution::refund_prepayment(env, group, &member);
     }
+    keeper::refund_pool(env, group);
 
-    let mut group = group;
     group.status = GroupStatus::Completed;
-    storage::set_group(env, &group);
+    storage::set_group(env, group);
+}
 
-    env.events()
-        .publish((crate::symbol_short!("emergenc"),), group_id);
+/// Pay out refunds in one token and split whatever else the group holds in it
+/// equally. The indivisible remainder is handed out one unit at a time in join order.
+fn refund_token(env: &Env, group: &SavingsGroup, token: &Address, refunds: &Map<Address, i128>) {
+    let balance = asset::escrow_balance(env, group, token);
+    let mut refunded: i128 = 0;
+    for amount in refunds.values() {
+        refunded += amount;
+    }
 
-    Ok(())
+    let member_count = group.members.len() as i128;
+    let surplus = balance - refunded;
+    let share = surplus / member_count;
+    let remainder = surplus % member_count;
+
+    let token_client = soroban_sdk::token::Client::new(env, token);
+    let contract_addr = env.current_contract_address();
+    for (index, member) in group.members.iter().enumerate() {
+        let mut amount = refunds.get(member.clone()).unwrap_or(0) + share;
+        if (index as i128) < remainder {
+            amount += 1;
+        }
+
+        if amount > 0 {
+            token_client.transfer(&contract_addr, &member, &amount);
+            env.events().publish(
+                (crate::symbol_short!("refund"),),
+                (group.id, member, token.clone(), amount),
+            );
+        }
+    }
+
+    // Former members only get back what they are owed
+    for (claimant, amount) in refunds.iter() {
+        if amount > 0 && !group.members.contains(&claimant) {
+            token_client.transfer(&contract_addr, &claimant, &amount);
+            env.events().publish(
+                (crate::symbol_short!("refund"),),
+                (group.id, claimant, token.clone(), amount),
+            );
+        }
+    }
+    asset::adjust_escrow(env, group, token, -balance);
 }
 
+/// Propose a new group admin. The role only moves once the new admin accepts,
+/// so a mistyped address cannot take over the group.
 pub fn set_group_admin(
     env: &Env,
     current_admin: Address,
@@ -159,17 +467,76 @@ pub fn set_group_admin(
 ) -> Result<(), ContractError> {
     current_admin.require_auth();
 
-    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
 
     if current_admin != group.admin {
         return Err(ContractError::Unauthorized);
     }
 
+    storage::set_pending_group_admin(env, group_id, &new_admin);
+
+    env.events()
+        .publish((crate::symbol_short!("adm_prop"),), (group_id, new_admin));
+
+    Ok(())
+}
+
+pub fn accept_group_admin(
+    env: &Env,
+    new_admin: Address,
+    group_id: u64,
+) -> Result<(), ContractError> {
+    new_admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    group::ensure_open(&group)?;
+
+    if storage::get_pending_group_admin(env, group_id) != Some(new_admin.clone()) {
+        return Err(ContractError::NoPendingAdmin);
+    }
+
     group.admin = new_admin.clone();
     storage::set_group(env, &group);
+    storage::remove_pending_group_admin(env, group_id);
 
     env.events()
         .publish((crate::symbol_short!("adm_chng"),), (group_id, new_admin));
 
     Ok(())
 }
+
+/// Propose a new protocol admin, to take over once they accept.
+pub fn propose_protocol_admin(
+    env: &Env,
+    admin: Address,
+    new_admin: Address,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    storage::set_pending_admin(env, &new_admin);
+
+    env.events()
+        .publish((crate::symbol_short!("padm_prp"),), new_admin);
+
+    Ok(())
+}
+
+pub fn accept_protocol_admin(env: &Env, new_admin: Address) -> Result<(), ContractError> {
+    new_admin.require_auth();
+
+    if storage::get_pending_admin(env) != Some(new_admin.clone()) {
+        return Err(ContractError::NoPendingAdmin);
+    }
+
+    storage::set_admin(env, &new_admin);
+    storage::remove_pending_admin(env);
+
+    env.events()
+        .publish((crate::symbol_short!("padm_chg"),), new_admin);
+
+    Ok(())
+}
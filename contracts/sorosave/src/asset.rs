@@ -0,0 +1,189 @@
+use soroban_sdk::{Address, Env, Map};
+
+use crate::errors::ContractError;
+use crate::oracle;
+use crate::storage;
+use crate::types::{GroupStatus, PayoutOrderStrategy, SavingsGroup};
+
+/// Exchange rates are fixed-point with 7 decimals: a rate of `RATE_SCALE` means
+/// one unit of the accepted token is worth one unit of the group's payout token.
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Accept another token for contributions at a fixed rate against the group's
+/// payout token. Rates are frozen once the group starts.
+pub fn add_accepted_token(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    token: Address,
+    rate: i128,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    // Auction discounts are settled in the payout token only
+    if group.payout_strategy == PayoutOrderStrategy::Auction {
+        return Err(ContractError::MultiAssetNotSupported);
+    }
+
+    if token == group.token || rate <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let mut tokens = storage::get_accepted_tokens(env, group_id);
+    tokens.set(token.clone(), rate);
+    storage::set_accepted_tokens(env, group_id, &tokens);
+
+    env.events()
+        .publish((crate::symbol_short!("tok_add"),), (group_id, token, rate));
+
+    Ok(())
+}
+
+pub fn remove_accepted_token(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    token: Address,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    let mut tokens = storage::get_accepted_tokens(env, group_id);
+    if !tokens.contains_key(token.clone()) {
+        return Err(ContractError::AssetNotAccepted);
+    }
+
+    // Members who picked this token fall back to the payout token
+    for m in group.members.iter() {
+        if storage::get_member_token(env, group_id, &m) == Some(token.clone()) {
+            storage::remove_member_token(env, group_id, &m);
+        }
+    }
+
+    tokens.remove(token.clone());
+    storage::set_accepted_tokens(env, group_id, &tokens);
+
+    env.events()
+        .publish((crate::symbol_short!("tok_rmv"),), (group_id, token));
+
+    Ok(())
+}
+
+/// Choose which accepted token a member pays their contributions in.
+pub fn set_contribution_token(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    token: Address,
+) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    if token == group.token {
+        storage::remove_member_token(env, group_id, &member);
+    } else if storage::get_accepted_tokens(env, group_id).contains_key(token.clone()) {
+        storage::set_member_token(env, group_id, &member, &token);
+    } else {
+        return Err(ContractError::AssetNotAccepted);
+    }
+
+    env.events().publish(
+        (crate::symbol_short!("tok_pick"),),
+        (group_id, member, token),
+    );
+
+    Ok(())
+}
+
+pub fn get_accepted_tokens(env: &Env, group_id: u64) -> Result<Map<Address, i128>, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_accepted_tokens(env, group_id))
+}
+
+pub fn get_contribution_token(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+) -> Result<Address, ContractError> {
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(contribution_token(env, &group, &member))
+}
+
+pub fn get_asset_balance(env: &Env, group_id: u64, token: Address) -> Result<i128, ContractError> {
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(escrow_balance(env, &group, &token))
+}
+
+/// The token a member pays contributions in.
+pub fn contribution_token(env: &Env, group: &SavingsGroup, member: &Address) -> Address {
+    storage::get_member_token(env, group.id, member).unwrap_or(group.token.clone())
+}
+
+/// Amount of `token` worth `value` in the group's denomination, rounded up so
+/// the pot is never short. Groups with a price oracle are denominated in its
+/// reference currency and price every token, the payout token included.
+pub fn to_token_amount(
+    env: &Env,
+    group: &SavingsGroup,
+    token: &Address,
+    value: i128,
+) -> Result<i128, ContractError> {
+    let (rate, scale) = match storage::get_price_oracle(env, group.id) {
+        Some(oracle) => oracle::price(env, &oracle, token)?,
+        None if *token == group.token => return Ok(value),
+        None => {
+            let rate = storage::get_accepted_tokens(env, group.id)
+                .get(token.clone())
+                .unwrap_or(RATE_SCALE);
+            (rate, RATE_SCALE)
+        }
+    };
+    Ok((value * scale + rate - 1) / rate)
+}
+
+/// The group's escrowed balance of `token`.
+pub fn escrow_balance(env: &Env, group: &SavingsGroup, token: &Address) -> i128 {
+    if *token == group.token {
+        storage::get_group_balance(env, group.id)
+    } else {
+        storage::get_asset_balance(env, group.id, token)
+    }
+}
+
+/// Adjust the group's escrowed balance of `token` by `delta`.
+pub fn adjust_escrow(env: &Env, group: &SavingsGroup, token: &Address, delta: i128) {
+    let balance = escrow_balance(env, group, token) + delta;
+    if *token == group.token {
+        storage::set_group_balance(env, group.id, balance);
+    } else {
+        storage::set_asset_balance(env, group.id, token, balance);
+    }
+}
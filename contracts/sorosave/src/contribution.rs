@@ -1,10 +1,35 @@
 use soroban_sdk::{Address, Env};
 
+use crate::asset;
 use crate::errors::ContractError;
+use crate::fee;
+use crate::keeper;
+use crate::payout;
+use crate::reputation;
 use crate::storage;
-use crate::types::{GroupStatus, RoundInfo};
+use crate::types::{GroupStatus, Prepayment, RoundInfo, SavingsGroup};
 
+/// Pay whatever is still owed for the current round.
 pub fn contribute(env: &Env, member: Address, group_id: u64) -> Result<(), ContractError> {
+    record_payment(env, member, group_id, None)
+}
+
+/// Pay an installment towards the current round's contribution.
+pub fn contribute_partial(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    amount: i128,
+) -> Result<(), ContractError> {
+    record_payment(env, member, group_id, Some(amount))
+}
+
+fn record_payment(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    amount: Option<i128>,
+) -> Result<(), ContractError> {
     member.require_auth();
 
     let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
@@ -32,25 +57,48 @@ pub fn contribute(env: &Env, member: Address, group_id: u64) -> Result<(), Contr
         return Err(ContractError::RoundNotActive);
     }
 
-    // Check if already contributed this round
-    if round_info.contributions.contains_key(member.clone()) {
+    // Check if already paid in full this round
+    let paid = round_info.contributions.get(member.clone()).unwrap_or(0);
+    let remaining = group.contribution_amount - paid;
+    if remaining <= 0 {
         return Err(ContractError::AlreadyContributed);
     }
 
-    // Transfer tokens from member to this contract
-    let token_client = soroban_sdk::token::Client::new(env, &group.token);
-    token_client.transfer(
-        &member,
-        &env.current_contract_address(),
-        &group.contribution_amount,
+    let value = match amount {
+        None => remaining,
+        Some(amount) if amount > 0 && amount <= remaining => amount,
+        Some(_) => return Err(ContractError::InvalidAmount),
+    };
+
+    // Transfer the payment, in the member's chosen token, to this contract
+    let token = asset::contribution_token(env, &group, &member);
+    let amount = asset::to_token_amount(env, &group, &token, value)?;
+    let token_client = soroban_sdk::token::Client::new(env, &token);
+    token_client.transfer(&member, &env.current_contract_address(), &amount);
+
+    // Any protocol fee is paid on top of the contribution
+    let protocol_fee = fee::contribution_fee(env, &token, amount);
+    fee::collect(env, group_id, &token, &member, protocol_fee);
+
+    // Record the payment, valued in the group's denomination
+    round_info.contributions.set(member.clone(), paid + value);
+    round_info.total_contributed += value;
+    round_info.asset_totals.set(
+        token.clone(),
+        round_info.asset_totals.get(token.clone()).unwrap_or(0) + amount,
+    );
+    round_info.payments.set(
+        member.clone(),
+        round_info.payments.get(member.clone()).unwrap_or(0) + amount,
     );
+    asset::adjust_escrow(env, &group, &token, amount);
 
-    // Record contribution
-    round_info.contributions.set(member.clone(), true);
-    round_info.total_contributed += group.contribution_amount;
+    let on_time = env.ledger().timestamp() <= round_info.deadline;
+    if paid + value >= group.contribution_amount {
+        reputation::record_payment(env, &member, on_time);
+    }
 
-    // Check if all members have contributed
-    if round_info.contributions.len() == group.members.len() {
+    if is_fully_paid(&group, &round_info) {
         round_info.is_complete = true;
     }
 
@@ -58,23 +106,268 @@ pub fn contribute(env: &Env, member: Address, group_id: u64) -> Result<(), Contr
 
     env.events().publish(
         (crate::symbol_short!("contrib"),),
-        (group_id, member, group.contribution_amount),
+        (group_id, member.clone(), value),
+    );
+
+    // Late contributions are still accepted until the round is closed
+    if !on_time {
+        env.events().publish(
+            (crate::symbol_short!("late_pay"),),
+            (group_id, member, round_info.round_number),
+        );
+    }
+
+    payout::auto_payout(env, group_id);
+
+    Ok(())
+}
+
+/// Pay the contributions for the next `rounds` rounds up front. They are credited
+/// as each new round opens, at the price locked in now.
+pub fn prepay(env: &Env, member: Address, group_id: u64, rounds: u32) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    // Only rounds that have yet to open can be prepaid
+    let mut prepayment = storage::get_prepayment(env, group_id, &member).unwrap_or(Prepayment {
+        rounds: 0,
+        amount: 0,
+    });
+    if rounds == 0 || group.current_round + prepayment.rounds + rounds > group.total_rounds {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let token = asset::contribution_token(env, &group, &member);
+    let amount =
+        asset::to_token_amount(env, &group, &token, group.contribution_amount)? * rounds as i128;
+    let token_client = soroban_sdk::token::Client::new(env, &token);
+    token_client.transfer(&member, &env.current_contract_address(), &amount);
+
+    let protocol_fee = fee::contribution_fee(env, &token, amount);
+    fee::collect(env, group_id, &token, &member, protocol_fee);
+
+    prepayment.rounds += rounds;
+    prepayment.amount += amount;
+    storage::set_prepayment(env, group_id, &member, &prepayment);
+
+    env.events().publish(
+        (crate::symbol_short!("prepay"),),
+        (group_id, member, rounds),
     );
 
     Ok(())
 }
 
+/// Credit prepaid contributions to a newly opened round.
+pub fn apply_prepayments(env: &Env, group: &SavingsGroup, round_info: &mut RoundInfo) {
+    for m in group.members.iter() {
+        let Some(mut prepayment) = storage::get_prepayment(env, group.id, &m) else {
+            continue;
+        };
+
+        // Rounding leftovers are carried into the last prepaid round
+        let token = asset::contribution_token(env, group, &m);
+        let amount = prepayment.amount / prepayment.rounds as i128;
+        round_info
+            .contributions
+            .set(m.clone(), group.contribution_amount);
+        round_info.total_contributed += group.contribution_amount;
+        round_info.asset_totals.set(
+            token.clone(),
+            round_info.asset_totals.get(token.clone()).unwrap_or(0) + amount,
+        );
+        round_info.payments.set(m.clone(), amount);
+        asset::adjust_escrow(env, group, &token, amount);
+        reputation::record_payment(env, &m, true);
+
+        prepayment.rounds -= 1;
+        prepayment.amount -= amount;
+        if prepayment.rounds == 0 {
+            storage::remove_prepayment(env, group.id, &m);
+        } else {
+            storage::set_prepayment(env, group.id, &m, &prepayment);
+        }
+
+        env.events().publish(
+            (crate::symbol_short!("prepaid"),),
+            (group.id, m, round_info.round_number),
+        );
+    }
+
+    if is_fully_paid(group, round_info) {
+        round_info.is_complete = true;
+    }
+}
+
+/// Return whatever a member still has prepaid.
+pub fn refund_prepayment(env: &Env, group: &SavingsGroup, member: &Address) {
+    let Some(prepayment) = storage::get_prepayment(env, group.id, member) else {
+        return;
+    };
+
+    let token = asset::contribution_token(env, group, member);
+    let token_client = soroban_sdk::token::Client::new(env, &token);
+    token_client.transfer(&env.current_contract_address(), member, &prepayment.amount);
+    storage::remove_prepayment(env, group.id, member);
+
+    env.events().publish(
+        (crate::symbol_short!("prep_rfnd"),),
+        (group.id, member.clone(), prepayment.amount),
+    );
+}
+
+/// Close the current round after its deadline, recording defaulters and covering
+/// their shortfall from collateral. Closing never waits on the price oracle. A
+/// keeper, if given, is rewarded for the call.
+pub fn close_round(
+    env: &Env,
+    group_id: u64,
+    keeper: Option<&Address>,
+) -> Result<(), ContractError> {
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    let mut round_info = storage::get_round(env, group_id, group.current_round)
+        .ok_or(ContractError::RoundNotActive)?;
+
+    if round_info.is_complete {
+        return Err(ContractError::RoundNotActive);
+    }
+
+    if env.ledger().timestamp() <= round_info.deadline {
+        return Err(ContractError::DeadlineNotReached);
+    }
+
+    // Every member who has not paid by the deadline is recorded as a defaulter
+    for m in group.members.iter() {
+        let paid = round_info.contributions.get(m.clone()).unwrap_or(0);
+        let shortfall = group.contribution_amount - paid;
+        if shortfall <= 0 {
+            continue;
+        }
+
+        let count = storage::get_default_count(env, group_id, &m) + 1;
+        storage::set_default_count(env, group_id, &m, count);
+        round_info.defaulters.push_back(m.clone());
+        reputation::record_default(env, &m);
+
+        env.events().publish(
+            (crate::symbol_short!("defaultd"),),
+            (group_id, m.clone(), round_info.round_number),
+        );
+
+        // Cover the shortfall from the defaulter's collateral. Without a fresh
+        // price the collateral is left alone; the default still stands.
+        let collateral = storage::get_collateral(env, group_id, &m);
+        if collateral <= 0 {
+            continue;
+        }
+        let Ok(required) = asset::to_token_amount(env, &group, &group.token, shortfall) else {
+            env.events().publish(
+                (crate::symbol_short!("slash_skp"),),
+                (group_id, m, round_info.round_number),
+            );
+            continue;
+        };
+        let slashed = collateral.min(required);
+        if slashed > 0 {
+            storage::set_collateral(env, group_id, &m, collateral - slashed);
+            round_info.total_contributed += slashed * shortfall / required;
+            round_info.asset_totals.set(
+                group.token.clone(),
+                round_info
+                    .asset_totals
+                    .get(group.token.clone())
+                    .unwrap_or(0)
+                    + slashed,
+            );
+            asset::adjust_escrow(env, &group, &group.token, slashed);
+
+            env.events()
+                .publish((crate::symbol_short!("slashed"),), (group_id, m, slashed));
+        }
+    }
+
+    // The round pays out whatever was collected
+    round_info.is_complete = true;
+    storage::set_round(env, group_id, &round_info);
+
+    env.events().publish(
+        (crate::symbol_short!("rnd_clos"),),
+        (
+            group_id,
+            round_info.round_number,
+            round_info.total_contributed,
+        ),
+    );
+
+    if let Some(keeper) = keeper {
+        keeper::reward(env, &group, keeper, 0);
+    }
+    payout::auto_payout(env, group_id);
+
+    Ok(())
+}
+
 pub fn get_round_status(env: &Env, group_id: u64, round: u32) -> Result<RoundInfo, ContractError> {
     storage::get_round(env, group_id, round).ok_or(ContractError::RoundNotActive)
 }
 
+/// Whether a member has paid a round's contribution in full.
 pub fn has_contributed(
     env: &Env,
     member: Address,
     group_id: u64,
     round: u32,
 ) -> Result<bool, ContractError> {
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
     let round_info =
         storage::get_round(env, group_id, round).ok_or(ContractError::RoundNotActive)?;
-    Ok(round_info.contributions.contains_key(member))
+    Ok(round_info.contributions.get(member).unwrap_or(0) >= group.contribution_amount)
+}
+
+/// How much a member has paid towards the current round so far.
+pub fn get_member_round_balance(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+) -> Result<i128, ContractError> {
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    let round_info = storage::get_round(env, group_id, group.current_round)
+        .ok_or(ContractError::RoundNotActive)?;
+    Ok(round_info.contributions.get(member).unwrap_or(0))
+}
+
+pub fn get_prepayment(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+) -> Result<Option<Prepayment>, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_prepayment(env, group_id, &member))
+}
+
+/// Whether every current member has paid the round's contribution in full.
+pub fn is_fully_paid(group: &SavingsGroup, round_info: &RoundInfo) -> bool {
+    group
+        .members
+        .iter()
+        .all(|m| round_info.contributions.get(m).unwrap_or(0) >= group.contribution_amount)
+}
+
+pub fn get_default_count(env: &Env, member: Address, group_id: u64) -> Result<u32, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_default_count(env, group_id, &member))
 }
@@ -22,4 +22,30 @@ pub enum ContractError {
     InsufficientMembers = 16,
     RoundNotComplete = 17,
     GroupCompleted = 18,
+    DeadlineNotReached = 19,
+    InvalidPayoutOrder = 20,
+    NotAuctionGroup = 21,
+    AuctionNotSettled = 22,
+    BiddingClosed = 23,
+    AlreadyPaidOut = 24,
+    AlreadyVoted = 25,
+    VotingClosed = 26,
+    VotingOpen = 27,
+    DisputeNotFound = 28,
+    DisputeResolved = 29,
+    InvalidFee = 30,
+    FeeNotConfigured = 31,
+    NoPendingAdmin = 32,
+    AlreadyMigrated = 33,
+    AssetNotAccepted = 34,
+    MultiAssetNotSupported = 35,
+    PriceUnavailable = 36,
+    NoDefaultRecorded = 37,
+    ReputationTooLow = 38,
+    NotInvited = 39,
+    InvalidInviteCode = 40,
+    AlreadyRequested = 41,
+    NoJoinRequest = 42,
+    GroupCancelled = 43,
+    InvalidMigrationRange = 44,
 }
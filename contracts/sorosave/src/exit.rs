@@ -0,0 +1,207 @@
+use soroban_sdk::{Address, Env, Map};
+
+use crate::asset;
+use crate::errors::ContractError;
+use crate::group;
+use crate::payout;
+use crate::storage;
+use crate::types::{GroupStatus, SavingsGroup};
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Set the penalty, in basis points of what a member has paid in, charged when
+/// they exit an active group. Only while forming, so members know it up front.
+pub fn set_exit_penalty(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    bps: u32,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    if bps as i128 > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    storage::set_exit_penalty(env, group_id, bps);
+
+    env.events()
+        .publish((crate::symbol_short!("exit_pen"),), (group_id, bps));
+
+    Ok(())
+}
+
+/// Leave an active group, settling what the member has paid into past rounds
+/// against what they have received, less the exit penalty. A member who owes
+/// the group pays the difference now; one who is owed is repaid from the
+/// remaining rounds' pots. Either way the settlement is spread evenly over the
+/// rounds still to come.
+pub fn exit_group(env: &Env, member: Address, group_id: u64) -> Result<i128, ContractError> {
+    member.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    let balance = settlement(env, &group, &member);
+    let amount = asset::to_token_amount(env, &group, &group.token, balance.abs())?;
+    if balance < 0 {
+        let token_client = soroban_sdk::token::Client::new(env, &group.token);
+        token_client.transfer(&member, &env.current_contract_address(), &amount);
+        asset::adjust_escrow(env, &group, &group.token, amount);
+        storage::set_exit_reserve(
+            env,
+            group_id,
+            storage::get_exit_reserve(env, group_id) + amount,
+        );
+    } else if balance > 0 {
+        let mut claims = storage::get_exit_claims(env, group_id);
+        claims.set(member.clone(), amount);
+        storage::set_exit_claims(env, group_id, &claims);
+    }
+
+    // Their current-round payment and collateral are returned as they leave
+    group::exit_active_member(env, &mut group, &member);
+    storage::set_group(env, &group);
+    payout::auto_payout(env, group_id);
+
+    env.events()
+        .publish((crate::symbol_short!("exit"),), (group_id, member, balance));
+
+    Ok(balance)
+}
+
+/// What a member would settle for if they exited now, in the group's
+/// denomination. Positive means the group owes the member.
+pub fn get_exit_settlement(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+) -> Result<i128, ContractError> {
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    Ok(settlement(env, &group, &member))
+}
+
+pub fn get_exit_penalty(env: &Env, group_id: u64) -> Result<u32, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_exit_penalty(env, group_id))
+}
+
+/// Paid in minus received minus the penalty, over the rounds already paid out.
+fn settlement(env: &Env, group: &SavingsGroup, member: &Address) -> i128 {
+    let mut paid: i128 = 0;
+    let mut received: i128 = 0;
+    for round in 1..group.current_round {
+        let Some(round_info) = storage::get_round(env, group.id, round) else {
+            continue;
+        };
+        paid += round_info.contributions.get(member.clone()).unwrap_or(0);
+        if round_info.recipient == *member {
+            received += round_info.total_contributed - round_info.discount;
+        }
+    }
+
+    let penalty = paid * storage::get_exit_penalty(env, group.id) as i128 / BPS_DENOMINATOR;
+    paid - received - penalty
+}
+
+/// Release this round's share of what exiting members paid in. It is already
+/// held in the group's escrow.
+pub fn release_reserve(env: &Env, group: &SavingsGroup) -> i128 {
+    let reserve = storage::get_exit_reserve(env, group.id);
+    if reserve <= 0 {
+        return 0;
+    }
+
+    let rounds_left = (group.total_rounds + 1)
+        .saturating_sub(group.current_round)
+        .max(1);
+    let release = reserve / rounds_left as i128;
+    storage::set_exit_reserve(env, group.id, reserve - release);
+    release
+}
+
+/// Pay this round's share of what is owed to exited members out of a pot of
+/// `available`, returning the total paid.
+pub fn pay_claims(env: &Env, group: &SavingsGroup, available: i128) -> i128 {
+    let mut claims = storage::get_exit_claims(env, group.id);
+    if claims.is_empty() {
+        return 0;
+    }
+
+    let rounds_left = (group.total_rounds + 1)
+        .saturating_sub(group.current_round)
+        .max(1);
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    let mut paid: i128 = 0;
+    for (claimant, claim) in claims.iter() {
+        let amount = (claim / rounds_left as i128).min(available - paid);
+        if amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &claimant, &amount);
+            paid += amount;
+
+            env.events().publish(
+                (crate::symbol_short!("exit_pay"),),
+                (group.id, claimant.clone(), amount),
+            );
+        }
+
+        if claim == amount {
+            claims.remove(claimant);
+        } else {
+            claims.set(claimant, claim - amount);
+        }
+    }
+    storage::set_exit_claims(env, group.id, &claims);
+    paid
+}
+
+/// Pay what is still owed to exited members out of the escrow left once the
+/// group completes, as far as it covers.
+pub fn settle_claims(env: &Env, group: &SavingsGroup) {
+    let mut available = storage::get_group_balance(env, group.id);
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    for (claimant, claim) in take_claims(env, group.id).iter() {
+        let amount = claim.min(available);
+        if amount <= 0 {
+            continue;
+        }
+        token_client.transfer(&env.current_contract_address(), &claimant, &amount);
+        available -= amount;
+
+        env.events().publish(
+            (crate::symbol_short!("exit_pay"),),
+            (group.id, claimant, amount),
+        );
+    }
+    storage::set_group_balance(env, group.id, available);
+}
+
+/// Take every outstanding exit claim, for a group that is being wound up.
+pub fn take_claims(env: &Env, group_id: u64) -> Map<Address, i128> {
+    let claims = storage::get_exit_claims(env, group_id);
+    storage::remove_exit_claims(env, group_id);
+    storage::remove_exit_reserve(env, group_id);
+    claims
+}
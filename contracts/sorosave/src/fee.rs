@@ -0,0 +1,165 @@
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::storage;
+use crate::types::{FeeConfig, FeeRates};
+
+/// Upper bound on any fee rate (10%).
+const MAX_FEE_BPS: u32 = 1_000;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+pub fn set_fee_config(
+    env: &Env,
+    admin: Address,
+    treasury: Address,
+    payout_bps: u32,
+    contribution_bps: u32,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let rates = FeeRates {
+        payout_bps,
+        contribution_bps,
+    };
+    check_rates(&rates)?;
+
+    storage::set_fee_config(env, &FeeConfig { treasury, rates });
+
+    env.events().publish(
+        (crate::symbol_short!("fee_cfg"),),
+        (payout_bps, contribution_bps),
+    );
+
+    Ok(())
+}
+
+/// Override the default fee rates for groups using a particular token.
+pub fn set_token_fee(
+    env: &Env,
+    admin: Address,
+    token: Address,
+    payout_bps: u32,
+    contribution_bps: u32,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let rates = FeeRates {
+        payout_bps,
+        contribution_bps,
+    };
+    check_rates(&rates)?;
+
+    storage::set_token_fee(env, &token, &rates);
+
+    env.events().publish(
+        (crate::symbol_short!("fee_tok"),),
+        (token, payout_bps, contribution_bps),
+    );
+
+    Ok(())
+}
+
+pub fn remove_token_fee(env: &Env, admin: Address, token: Address) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    storage::remove_token_fee(env, &token);
+
+    env.events()
+        .publish((crate::symbol_short!("fee_rmv"),), token);
+
+    Ok(())
+}
+
+/// Set the share of each payout fee, in basis points, paid to whoever triggers the payout.
+pub fn set_keeper_fee_share(env: &Env, admin: Address, bps: u32) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if bps as i128 > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidFee);
+    }
+
+    storage::set_keeper_fee_share(env, bps);
+
+    env.events()
+        .publish((crate::symbol_short!("kpr_shr"),), bps);
+
+    Ok(())
+}
+
+pub fn get_keeper_fee_share(env: &Env) -> u32 {
+    storage::get_keeper_fee_share(env)
+}
+
+/// The keeper's cut of a protocol fee.
+pub fn keeper_share(env: &Env, fee: i128) -> i128 {
+    fee * storage::get_keeper_fee_share(env) as i128 / BPS_DENOMINATOR
+}
+
+pub fn get_fee_config(env: &Env) -> Result<FeeConfig, ContractError> {
+    storage::get_fee_config(env).ok_or(ContractError::FeeNotConfigured)
+}
+
+/// Fee rates that apply to a token, taking per-token overrides into account. No
+/// fees are charged until a treasury has been configured.
+pub fn get_fee_rates(env: &Env, token: Address) -> FeeRates {
+    match storage::get_fee_config(env) {
+        Some(config) => storage::get_token_fee(env, &token).unwrap_or(config.rates),
+        None => FeeRates {
+            payout_bps: 0,
+            contribution_bps: 0,
+        },
+    }
+}
+
+/// Fee owed on a payout of `amount`.
+pub fn payout_fee(env: &Env, token: &Address, amount: i128) -> i128 {
+    let rates = get_fee_rates(env, token.clone());
+    amount * rates.payout_bps as i128 / BPS_DENOMINATOR
+}
+
+/// Fee owed on top of a contribution of `amount`.
+pub fn contribution_fee(env: &Env, token: &Address, amount: i128) -> i128 {
+    let rates = get_fee_rates(env, token.clone());
+    amount * rates.contribution_bps as i128 / BPS_DENOMINATOR
+}
+
+/// Send a fee from `from` to the treasury and record it against the group.
+pub fn collect(env: &Env, group_id: u64, token: &Address, from: &Address, fee: i128) {
+    if fee <= 0 {
+        return;
+    }
+    let Some(config) = storage::get_fee_config(env) else {
+        return;
+    };
+
+    let token_client = soroban_sdk::token::Client::new(env, token);
+    token_client.transfer(from, &config.treasury, &fee);
+
+    env.events().publish(
+        (crate::symbol_short!("fee"),),
+        (group_id, token.clone(), fee),
+    );
+}
+
+fn check_rates(rates: &FeeRates) -> Result<(), ContractError> {
+    if rates.payout_bps > MAX_FEE_BPS || rates.contribution_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFee);
+    }
+    Ok(())
+}
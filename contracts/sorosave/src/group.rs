@@ -1,9 +1,17 @@
 use soroban_sdk::{Address, Env, Map, String, Vec};
 
+use crate::access;
+use crate::admin;
+use crate::asset;
+use crate::contribution;
 use crate::errors::ContractError;
+use crate::keeper;
+use crate::payout;
+use crate::reputation;
 use crate::storage;
-use crate::types::{GroupStatus, RoundInfo, SavingsGroup};
+use crate::types::{GroupStatus, GroupVisibility, PayoutOrderStrategy, RoundInfo, SavingsGroup};
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_group(
     env: &Env,
     admin: Address,
@@ -12,10 +20,13 @@ pub fn create_group(
     contribution_amount: i128,
     cycle_length: u64,
     max_members: u32,
+    collateral: i128,
+    payout_strategy: PayoutOrderStrategy,
+    min_reputation: u32,
 ) -> Result<u64, ContractError> {
     admin.require_auth();
 
-    if contribution_amount <= 0 {
+    if contribution_amount <= 0 || collateral < 0 {
         return Err(ContractError::InvalidAmount);
     }
     if max_members < 2 {
@@ -36,16 +47,25 @@ pub fn create_group(
         contribution_amount,
         cycle_length,
         max_members,
+        collateral,
         members,
+        payout_strategy,
         payout_order: Vec::new(env),
         current_round: 0,
         total_rounds: 0,
         status: GroupStatus::Forming,
         created_at: env.ledger().timestamp(),
+        auto_payout: false,
+        transfer_approval: false,
+        min_reputation,
+        visibility: GroupVisibility::Public,
+        auto_start_at: 0,
+        auto_start_when_full: false,
     };
 
     storage::set_group(env, &group);
     storage::add_member_group(env, &admin, group_id);
+    lock_collateral(env, &group, &admin);
 
     env.events()
         .publish((crate::symbol_short!("grp_creat"),), group_id);
@@ -57,6 +77,18 @@ pub fn join_group(env: &Env, member: Address, group_id: u64) -> Result<(), Contr
     member.require_auth();
 
     let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    access::check_can_join(env, &group, &member)?;
+
+    admit_member(env, &mut group, member)
+}
+
+/// Add a member to a forming group once they have been let in.
+pub fn admit_member(
+    env: &Env,
+    group: &mut SavingsGroup,
+    member: Address,
+) -> Result<(), ContractError> {
+    let group_id = group.id;
 
     if group.status != GroupStatus::Forming {
         return Err(ContractError::GroupNotForming);
@@ -73,13 +105,31 @@ pub fn join_group(env: &Env, member: Address, group_id: u64) -> Result<(), Contr
         }
     }
 
+    if reputation::get_reputation(env, member.clone()).score < group.min_reputation {
+        return Err(ContractError::ReputationTooLow);
+    }
+
+    // Joining settles any join request still pending
+    let mut requests = storage::get_join_requests(env, group_id);
+    if let Some(i) = requests.first_index_of(&member) {
+        requests.remove(i);
+        storage::set_join_requests(env, group_id, &requests);
+    }
+
     group.members.push_back(member.clone());
-    storage::set_group(env, &group);
+    storage::set_group(env, group);
     storage::add_member_group(env, &member, group_id);
+    lock_collateral(env, group, &member);
 
     env.events()
         .publish((crate::symbol_short!("grp_join"),), (group_id, member));
 
+    // A group whose payout order does not fit its members waits for the admin
+    if is_due_to_start(env, group) && activate(env, group).is_err() {
+        env.events()
+            .publish((crate::symbol_short!("strt_fail"),), group_id);
+    }
+
     Ok(())
 }
 
@@ -97,26 +147,158 @@ pub fn leave_group(env: &Env, member: Address, group_id: u64) -> Result<(), Cont
         return Err(ContractError::Unauthorized);
     }
 
-    let mut found = false;
-    let mut new_members = Vec::new(env);
-    for m in group.members.iter() {
-        if m == member {
-            found = true;
-        } else {
-            new_members.push_back(m);
-        }
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
     }
 
-    if !found {
+    remove_forming_member(env, &mut group, &member);
+
+    env.events()
+        .publish((crate::symbol_short!("grp_leav"),), (group_id, member));
+
+    Ok(())
+}
+
+/// Remove a member from a group. While the group is forming the admin may remove
+/// anyone; once it is active only a member with a recorded default can be removed
+/// this way, and anyone else needs a `RemoveMember` dispute vote.
+pub fn remove_member(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    member: Address,
+    reason: String,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin || member == group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if !group.members.contains(&member) {
         return Err(ContractError::NotMember);
     }
 
-    group.members = new_members;
+    match group.status {
+        GroupStatus::Forming => remove_forming_member(env, &mut group, &member),
+        GroupStatus::Active => {
+            if storage::get_default_count(env, group_id, &member) == 0 {
+                return Err(ContractError::NoDefaultRecorded);
+            }
+            remove_active_member(env, &mut group, &member);
+            storage::set_group(env, &group);
+            payout::auto_payout(env, group_id);
+        }
+        _ => return Err(ContractError::GroupNotActive),
+    }
+
+    env.events().publish(
+        (crate::symbol_short!("grp_kick"),),
+        (group_id, member, reason),
+    );
+
+    Ok(())
+}
+
+/// Hand a member's seat in an active group to someone new. The newcomer takes
+/// over the seat's payout position, collateral, default record and whatever was
+/// paid into the current round.
+pub fn transfer_seat(
+    env: &Env,
+    from: Address,
+    to: Address,
+    group_id: u64,
+) -> Result<(), ContractError> {
+    from.require_auth();
+    to.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.transfer_approval && from != group.admin {
+        group.admin.require_auth();
+    }
+
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    let seat = group
+        .members
+        .first_index_of(&from)
+        .ok_or(ContractError::NotMember)?;
+    if group.members.contains(&to) {
+        return Err(ContractError::AlreadyMember);
+    }
+    if reputation::get_reputation(env, to.clone()).score < group.min_reputation {
+        return Err(ContractError::ReputationTooLow);
+    }
+
+    group.members.set(seat, to.clone());
+    if let Some(i) = group.payout_order.first_index_of(&from) {
+        group.payout_order.set(i, to.clone());
+    }
+    if group.admin == from {
+        group.admin = to.clone();
+        storage::remove_pending_group_admin(env, group_id);
+    }
+    storage::remove_member_group(env, &from, group_id);
+    storage::add_member_group(env, &to, group_id);
+
+    // Per-member records follow the seat
+    let collateral = storage::get_collateral(env, group_id, &from);
+    if collateral > 0 {
+        storage::set_collateral(env, group_id, &to, collateral);
+        storage::remove_collateral(env, group_id, &from);
+    }
+    let defaults = storage::get_default_count(env, group_id, &from);
+    if defaults > 0 {
+        storage::set_default_count(env, group_id, &to, defaults);
+        storage::remove_default_count(env, group_id, &from);
+    }
+    if let Some(token) = storage::get_member_token(env, group_id, &from) {
+        storage::set_member_token(env, group_id, &to, &token);
+        storage::remove_member_token(env, group_id, &from);
+    }
+    if let Some(prepayment) = storage::get_prepayment(env, group_id, &from) {
+        storage::set_prepayment(env, group_id, &to, &prepayment);
+        storage::remove_prepayment(env, group_id, &from);
+    }
+
+    // So does the seat's history, which exit settlements are computed from
+    for round in 1..=group.current_round {
+        let Some(mut round_info) = storage::get_round(env, group_id, round) else {
+            continue;
+        };
+        if let Some(paid) = round_info.contributions.get(from.clone()) {
+            round_info.contributions.remove(from.clone());
+            round_info.contributions.set(to.clone(), paid);
+        }
+        if let Some(amount) = round_info.payments.get(from.clone()) {
+            round_info.payments.remove(from.clone());
+            round_info.payments.set(to.clone(), amount);
+        }
+        if round_info.recipient == from {
+            round_info.recipient = to.clone();
+        }
+        if let Some(i) = round_info.defaulters.first_index_of(&from) {
+            round_info.defaulters.set(i, to.clone());
+        }
+        storage::set_round(env, group_id, &round_info);
+    }
+
+    let mut bids = storage::get_auction_bids(env, group_id, group.current_round);
+    if let Some(bid) = bids.get(from.clone()) {
+        bids.remove(from.clone());
+        bids.set(to.clone(), bid);
+        storage::set_auction_bids(env, group_id, group.current_round, &bids);
+    }
+
     storage::set_group(env, &group);
-    storage::remove_member_group(env, &member, group_id);
 
     env.events()
-        .publish((crate::symbol_short!("grp_leav"),), (group_id, member));
+        .publish((crate::symbol_short!("seat_xfer"),), (group_id, from, to));
 
     Ok(())
 }
@@ -134,18 +316,76 @@ pub fn start_group(env: &Env, admin: Address, group_id: u64) -> Result<(), Contr
         return Err(ContractError::GroupNotForming);
     }
 
+    activate(env, &mut group)
+}
+
+/// Start a group whose scheduled start time has passed or that filled up with
+/// `auto_start_when_full` set. Anyone can call this.
+pub fn try_start(env: &Env, group_id: u64) -> Result<(), ContractError> {
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    if !is_due_to_start(env, &group) {
+        return Err(ContractError::DeadlineNotReached);
+    }
+
+    activate(env, &mut group)
+}
+
+/// Schedule a group to start at `start_at` (0 for no scheduled start) and/or as
+/// soon as its last seat is taken.
+pub fn set_auto_start(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    start_at: u64,
+    when_full: bool,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    group.auto_start_at = start_at;
+    group.auto_start_when_full = when_full;
+    storage::set_group(env, &group);
+
+    env.events().publish(
+        (crate::symbol_short!("auto_strt"),),
+        (group_id, start_at, when_full),
+    );
+
+    Ok(())
+}
+
+fn is_due_to_start(env: &Env, group: &SavingsGroup) -> bool {
+    (group.auto_start_at > 0 && env.ledger().timestamp() >= group.auto_start_at)
+        || (group.auto_start_when_full && group.members.len() >= group.max_members)
+}
+
+/// Move a forming group to `Active`: fix the payout order and open round 1.
+fn activate(env: &Env, group: &mut SavingsGroup) -> Result<(), ContractError> {
     if group.members.len() < 2 {
         return Err(ContractError::InsufficientMembers);
     }
 
-    // Set payout order to member join order (can be randomized later)
-    group.payout_order = group.members.clone();
+    group.payout_order = build_payout_order(env, group)?;
     group.total_rounds = group.members.len();
     group.current_round = 1;
     group.status = GroupStatus::Active;
 
     // Initialize first round
-    let first_recipient = group.payout_order.get(0).unwrap();
+    let first_recipient = payout::scheduled_recipient(group);
     let round_info = RoundInfo {
         round_number: 1,
         recipient: first_recipient,
@@ -153,21 +393,458 @@ pub fn start_group(env: &Env, admin: Address, group_id: u64) -> Result<(), Contr
         total_contributed: 0,
         is_complete: false,
         deadline: env.ledger().timestamp() + group.cycle_length,
+        defaulters: Vec::new(env),
+        discount: 0,
+        asset_totals: Map::new(env),
+        payments: Map::new(env),
     };
 
-    storage::set_round(env, group_id, &round_info);
+    storage::set_round(env, group.id, &round_info);
+    storage::set_group(env, group);
+
+    // Requests still pending can no longer be admitted
+    access::drop_join_requests(env, group);
+
+    env.events()
+        .publish((crate::symbol_short!("grp_strt"),), group.id);
+
+    Ok(())
+}
+
+/// Abandon a group that has not started. Every member and pending join request
+/// gets their collateral and position bid back, and the group drops out of
+/// every member's group list.
+pub fn cancel_group(env: &Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    for member in group.members.iter() {
+        storage::remove_member_group(env, &member, group_id);
+        storage::remove_member_token(env, group_id, &member);
+        release_collateral(env, &group, &member);
+        refund_position_bid(env, &group, &member);
+    }
+    access::drop_join_requests(env, &group);
+    keeper::refund_pool(env, &group);
+
+    group.status = GroupStatus::Cancelled;
     storage::set_group(env, &group);
 
     env.events()
-        .publish((crate::symbol_short!("grp_strt"),), group_id);
+        .publish((crate::symbol_short!("grp_cncl"),), group_id);
 
     Ok(())
 }
 
+/// Fail for groups that have finished, one way or another.
+pub fn ensure_open(group: &SavingsGroup) -> Result<(), ContractError> {
+    match group.status {
+        GroupStatus::Completed => Err(ContractError::GroupCompleted),
+        GroupStatus::Cancelled => Err(ContractError::GroupCancelled),
+        _ => Ok(()),
+    }
+}
+
+/// Set the payout order for a group using the `Explicit` strategy. The order is
+/// checked against the final member list when the group starts.
+pub fn set_payout_order(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    order: Vec<Address>,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    if group.payout_strategy != PayoutOrderStrategy::Explicit {
+        return Err(ContractError::InvalidPayoutOrder);
+    }
+
+    group.payout_order = order;
+    storage::set_group(env, &group);
+
+    env.events()
+        .publish((crate::symbol_short!("ord_set"),), group_id);
+
+    Ok(())
+}
+
+/// Opt a group in or out of paying out inline with the contribution that
+/// completes each round.
+pub fn set_auto_payout(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    enabled: bool,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    ensure_open(&group)?;
+
+    group.auto_payout = enabled;
+    storage::set_group(env, &group);
+
+    env.events()
+        .publish((crate::symbol_short!("auto_pay"),), (group_id, enabled));
+
+    Ok(())
+}
+
+/// Require the group admin to sign off on seat transfers.
+pub fn set_transfer_approval(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    required: bool,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    ensure_open(&group)?;
+
+    group.transfer_approval = required;
+    storage::set_group(env, &group);
+
+    env.events()
+        .publish((crate::symbol_short!("xfer_appr"),), (group_id, required));
+
+    Ok(())
+}
+
+/// Bid for an early payout position in a group using the `Bid` strategy. The bid
+/// is escrowed and can only be raised; the premiums are paid out with the final
+/// round to the member who waited longest.
+pub fn bid_for_position(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    amount: i128,
+) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    if group.payout_strategy != PayoutOrderStrategy::Bid {
+        return Err(ContractError::InvalidPayoutOrder);
+    }
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
+    }
+
+    let mut bids = storage::get_position_bids(env, group_id);
+    let previous = bids.get(member.clone()).unwrap_or(0);
+    if amount <= previous {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    token_client.transfer(
+        &member,
+        &env.current_contract_address(),
+        &(amount - previous),
+    );
+    storage::set_group_balance(
+        env,
+        group_id,
+        storage::get_group_balance(env, group_id) + amount - previous,
+    );
+
+    bids.set(member.clone(), amount);
+    storage::set_position_bids(env, group_id, &bids);
+
+    env.events().publish(
+        (crate::symbol_short!("pos_bid"),),
+        (group_id, member, amount),
+    );
+
+    Ok(())
+}
+
+/// Work out the payout order for a group that is about to start.
+fn build_payout_order(env: &Env, group: &SavingsGroup) -> Result<Vec<Address>, ContractError> {
+    match group.payout_strategy {
+        PayoutOrderStrategy::JoinOrder => Ok(group.members.clone()),
+        // Filled in round by round as auctions settle
+        PayoutOrderStrategy::Auction => Ok(Vec::new(env)),
+        PayoutOrderStrategy::Random => {
+            let mut order = group.members.clone();
+            env.prng().shuffle(&mut order);
+            Ok(order)
+        }
+        PayoutOrderStrategy::Explicit => {
+            // Every member must appear exactly once
+            let order = group.payout_order.clone();
+            if order.len() != group.members.len() {
+                return Err(ContractError::InvalidPayoutOrder);
+            }
+            for m in group.members.iter() {
+                match order.first_index_of(&m) {
+                    Some(i) if order.last_index_of(&m) == Some(i) => {}
+                    _ => return Err(ContractError::InvalidPayoutOrder),
+                }
+            }
+            Ok(order)
+        }
+        PayoutOrderStrategy::Bid => {
+            // Highest bid first; ties keep join order
+            let bids = storage::get_position_bids(env, group.id);
+            let mut order: Vec<Address> = Vec::new(env);
+            let mut pool: i128 = 0;
+            for m in group.members.iter() {
+                let bid = bids.get(m.clone()).unwrap_or(0);
+                pool += bid;
+
+                let mut position = order.len();
+                for (i, placed) in order.iter().enumerate() {
+                    if bids.get(placed).unwrap_or(0) < bid {
+                        position = i as u32;
+                        break;
+                    }
+                }
+                order.insert(position, m);
+            }
+
+            storage::remove_position_bids(env, group.id);
+            if pool > 0 {
+                storage::set_bid_pool(env, group.id, pool);
+            }
+            Ok(order)
+        }
+    }
+}
+
+/// Take a member out of a group that has not started, returning their collateral
+/// and any position bid.
+fn remove_forming_member(env: &Env, group: &mut SavingsGroup, member: &Address) {
+    if let Some(i) = group.members.first_index_of(member) {
+        group.members.remove(i);
+    }
+    storage::set_group(env, group);
+    storage::remove_member_group(env, member, group.id);
+    storage::remove_member_token(env, group.id, member);
+    release_collateral(env, group, member);
+    refund_position_bid(env, group, member);
+}
+
+/// Return a member's position bid when they leave a forming group.
+fn refund_position_bid(env: &Env, group: &SavingsGroup, member: &Address) {
+    let mut bids = storage::get_position_bids(env, group.id);
+    let amount = bids.get(member.clone()).unwrap_or(0);
+    if amount <= 0 {
+        return;
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    token_client.transfer(&env.current_contract_address(), member, &amount);
+    storage::set_group_balance(
+        env,
+        group.id,
+        storage::get_group_balance(env, group.id) - amount,
+    );
+
+    bids.remove(member.clone());
+    storage::set_position_bids(env, group.id, &bids);
+}
+
+/// Remove a member from a running group, by the admin or a dispute vote. A
+/// member who has already been paid out leaves their collateral behind to cover
+/// the contributions they still owe; anyone else gets it back. The caller
+/// persists the group.
+pub fn remove_active_member(env: &Env, group: &mut SavingsGroup, member: &Address) {
+    let paid_out = group
+        .payout_order
+        .first_index_of(member)
+        .is_some_and(|i| i + 1 < group.current_round);
+    if paid_out {
+        forfeit_collateral(env, group, member);
+    } else {
+        release_collateral(env, group, member);
+    }
+    drop_active_member(env, group, member);
+}
+
+/// Let a member leave a running group with their collateral. What they owe or
+/// are owed is settled separately by `exit::exit_group`. The caller persists
+/// the group.
+pub fn exit_active_member(env: &Env, group: &mut SavingsGroup, member: &Address) {
+    release_collateral(env, group, member);
+    drop_active_member(env, group, member);
+}
+
+/// Take a member out of a running group. Their unpaid payout slot is dropped,
+/// anything they paid into the current round is returned, and the group is
+/// wound up if it can no longer run.
+fn drop_active_member(env: &Env, group: &mut SavingsGroup, member: &Address) {
+    if let Some(i) = group.members.first_index_of(member) {
+        group.members.remove(i);
+    }
+
+    // Slots for rounds already paid out are history and stay in place
+    match group.payout_order.first_index_of(member) {
+        Some(i) if i + 1 < group.current_round => {}
+        Some(i) => {
+            group.payout_order.remove(i);
+            group.total_rounds -= 1;
+        }
+        // Unpaid auction members hold no slot yet
+        None => group.total_rounds -= 1,
+    }
+
+    if let Some(mut round_info) = storage::get_round(env, group.id, group.current_round) {
+        if let Some(paid) = round_info.contributions.get(member.clone()) {
+            let token = asset::contribution_token(env, group, member);
+            let amount = round_info.payments.get(member.clone()).unwrap_or(0);
+            let token_client = soroban_sdk::token::Client::new(env, &token);
+            token_client.transfer(&env.current_contract_address(), member, &amount);
+            round_info.contributions.remove(member.clone());
+            round_info.payments.remove(member.clone());
+            round_info.total_contributed -= paid;
+            round_info.asset_totals.set(
+                token.clone(),
+                round_info.asset_totals.get(token.clone()).unwrap_or(0) - amount,
+            );
+            asset::adjust_escrow(env, group, &token, -amount);
+        }
+
+        if contribution::is_fully_paid(group, &round_info) {
+            round_info.is_complete = true;
+        }
+
+        if round_info.recipient == *member && group.current_round <= group.total_rounds {
+            round_info.recipient = payout::scheduled_recipient(group);
+            round_info.discount = 0;
+        }
+
+        storage::set_round(env, group.id, &round_info);
+    }
+
+    storage::remove_member_group(env, member, group.id);
+    contribution::refund_prepayment(env, group, member);
+    storage::remove_member_token(env, group.id, member);
+
+    if group.admin == *member {
+        if let Some(next_admin) = group.members.get(0) {
+            group.admin = next_admin;
+            storage::remove_pending_group_admin(env, group.id);
+        }
+    }
+
+    env.events().publish(
+        (crate::symbol_short!("mbr_rmvd"),),
+        (group.id, member.clone()),
+    );
+
+    if group.members.len() < 2 || group.current_round > group.total_rounds {
+        admin::unwind_group(env, group);
+    }
+}
+
+/// Pull the group's collateral requirement from a new member into escrow.
+/// Collateral already held since a join request is not taken twice.
+pub fn lock_collateral(env: &Env, group: &SavingsGroup, member: &Address) {
+    if group.collateral <= 0 || storage::get_collateral(env, group.id, member) > 0 {
+        return;
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    token_client.transfer(member, &env.current_contract_address(), &group.collateral);
+    storage::set_collateral(env, group.id, member, group.collateral);
+
+    env.events().publish(
+        (crate::symbol_short!("col_lock"),),
+        (group.id, member.clone(), group.collateral),
+    );
+}
+
+/// Return whatever collateral a member still has locked in the group.
+pub fn release_collateral(env: &Env, group: &SavingsGroup, member: &Address) -> i128 {
+    let amount = storage::get_collateral(env, group.id, member);
+    if amount <= 0 {
+        return 0;
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    token_client.transfer(&env.current_contract_address(), member, &amount);
+    storage::remove_collateral(env, group.id, member);
+
+    env.events().publish(
+        (crate::symbol_short!("col_rel"),),
+        (group.id, member.clone(), amount),
+    );
+
+    amount
+}
+
+/// Move a member's collateral into the group's escrow. Like an exit payment it is
+/// shared out over the remaining rounds' pots.
+fn forfeit_collateral(env: &Env, group: &SavingsGroup, member: &Address) {
+    let amount = storage::get_collateral(env, group.id, member);
+    if amount <= 0 {
+        return;
+    }
+
+    storage::remove_collateral(env, group.id, member);
+    asset::adjust_escrow(env, group, &group.token, amount);
+    storage::set_exit_reserve(
+        env,
+        group.id,
+        storage::get_exit_reserve(env, group.id) + amount,
+    );
+
+    env.events().publish(
+        (crate::symbol_short!("col_forf"),),
+        (group.id, member.clone(), amount),
+    );
+}
+
+pub fn get_collateral(env: &Env, member: Address, group_id: u64) -> Result<i128, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_collateral(env, group_id, &member))
+}
+
 pub fn get_group(env: &Env, group_id: u64) -> Result<SavingsGroup, ContractError> {
     storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)
 }
 
+pub fn get_group_balance(env: &Env, group_id: u64) -> Result<i128, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_group_balance(env, group_id))
+}
+
 pub fn get_member_groups(env: &Env, member: Address) -> Vec<u64> {
     storage::get_member_groups(env, &member)
 }
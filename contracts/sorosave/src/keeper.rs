@@ -0,0 +1,138 @@
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::group;
+use crate::storage;
+use crate::types::SavingsGroup;
+
+/// Add funds to a group's keeper pool. The pool pays the group's per-call keeper
+/// reward; what is left when the group ends is returned to the funders.
+pub fn fund_keeper_pool(
+    env: &Env,
+    funder: Address,
+    group_id: u64,
+    amount: i128,
+) -> Result<(), ContractError> {
+    funder.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    group::ensure_open(&group)?;
+
+    if amount <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    token_client.transfer(&funder, &env.current_contract_address(), &amount);
+    storage::set_keeper_pool(
+        env,
+        group_id,
+        storage::get_keeper_pool(env, group_id) + amount,
+    );
+    let mut funders = storage::get_keeper_funders(env, group_id);
+    funders.set(
+        funder.clone(),
+        funders.get(funder.clone()).unwrap_or(0) + amount,
+    );
+    storage::set_keeper_funders(env, group_id, &funders);
+
+    env.events().publish(
+        (crate::symbol_short!("kpr_fund"),),
+        (group_id, funder, amount),
+    );
+
+    Ok(())
+}
+
+/// Set how much the keeper pool pays for each payout or round close.
+pub fn set_keeper_reward(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    amount: i128,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    group::ensure_open(&group)?;
+
+    if amount < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    storage::set_keeper_reward(env, group_id, amount);
+
+    env.events()
+        .publish((crate::symbol_short!("kpr_rwd"),), (group_id, amount));
+
+    Ok(())
+}
+
+pub fn get_keeper_pool(env: &Env, group_id: u64) -> Result<i128, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_keeper_pool(env, group_id))
+}
+
+pub fn get_keeper_reward(env: &Env, group_id: u64) -> Result<i128, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_keeper_reward(env, group_id))
+}
+
+/// Pay a keeper their share of the protocol fee plus the group's reward, as far
+/// as the pool covers it.
+pub fn reward(env: &Env, group: &SavingsGroup, keeper: &Address, fee_share: i128) {
+    let pool = storage::get_keeper_pool(env, group.id);
+    let from_pool = storage::get_keeper_reward(env, group.id).min(pool);
+    let amount = fee_share + from_pool;
+    if amount <= 0 {
+        return;
+    }
+
+    storage::set_keeper_pool(env, group.id, pool - from_pool);
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    token_client.transfer(&env.current_contract_address(), keeper, &amount);
+
+    env.events().publish(
+        (crate::symbol_short!("keeper"),),
+        (group.id, keeper.clone(), amount),
+    );
+}
+
+/// Return whatever is left in an ended group's keeper pool to its funders, in
+/// proportion to what each put in. Rounding dust goes to the last funder.
+pub fn refund_pool(env: &Env, group: &SavingsGroup) {
+    let pool = storage::get_keeper_pool(env, group.id);
+    let funders = storage::get_keeper_funders(env, group.id);
+    storage::remove_keeper_pool(env, group.id);
+    storage::remove_keeper_funders(env, group.id);
+    if pool <= 0 {
+        return;
+    }
+
+    let funded: i128 = funders.values().iter().sum();
+    let token_client = soroban_sdk::token::Client::new(env, &group.token);
+    let mut remaining = pool;
+    for (i, (funder, amount)) in funders.iter().enumerate() {
+        let share = if i as u32 + 1 == funders.len() {
+            remaining
+        } else {
+            pool * amount / funded
+        };
+        if share <= 0 {
+            continue;
+        }
+        remaining -= share;
+        token_client.transfer(&env.current_contract_address(), &funder, &share);
+
+        env.events().publish(
+            (crate::symbol_short!("kpr_rfnd"),),
+            (group.id, funder, share),
+        );
+    }
+}
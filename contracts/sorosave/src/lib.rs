@@ -1,14 +1,24 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, Address, Bytes, BytesN, Env, Map, String, Vec,
+};
 
+mod access;
 mod admin;
+mod asset;
 mod contribution;
 mod errors;
+mod exit;
+mod fee;
 mod group;
+mod keeper;
+mod oracle;
 mod payout;
+mod reputation;
 mod storage;
 mod types;
+mod upgrade;
 
 pub use errors::ContractError;
 pub use types::*;
@@ -24,32 +34,44 @@ impl SoroSaveContract {
             panic!("already initialized");
         }
         storage::set_admin(&env, &admin);
+        storage::set_schema_version(&env, upgrade::SCHEMA_VERSION);
     }
 
-    // ─── Group Lifecycle ────────────────────────────────────────────
+    // ─── Upgrades ───────────────────────────────────────────────────
 
-    /// Create a new savings group. The caller becomes the group admin and first member.
-    pub fn create_group(
+    /// Replace the contract code with an uploaded WASM (protocol admin only).
+    pub fn upgrade(
         env: Env,
         admin: Address,
-        name: String,
-        token: Address,
-        contribution_amount: i128,
-        cycle_length: u64,
-        max_members: u32,
-    ) -> Result<u64, ContractError> {
-        group::create_group(
-            &env,
-            admin,
-            name,
-            token,
-            contribution_amount,
-            cycle_length,
-            max_members,
-        )
-    }
-
-    /// Join an existing group that is still forming.
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        upgrade::upgrade(&env, admin, new_wasm_hash)
+    }
+
+    /// Rewrite stored groups, rounds and disputes into the current storage layout
+    /// (protocol admin only), for groups `start_id..=end_id`. Run in consecutive
+    /// batches from group 1. Returns the schema version, which only moves once
+    /// the last group is migrated.
+    pub fn migrate(
+        env: Env,
+        admin: Address,
+        start_id: u64,
+        end_id: u64,
+    ) -> Result<u32, ContractError> {
+        upgrade::migrate(&env, admin, start_id, end_id)
+    }
+
+    /// Get the storage schema version currently in use.
+    pub fn get_schema_version(env: Env) -> u32 {
+        upgrade::get_schema_version(&env)
+    }
+
+    // ─── Group Lifecycle ────────────────────────────────────────────
+
+    // `create_group` is declared in the `create` module at the end of this file.
+
+    /// Join an existing group that is still forming. Locks the group's collateral, if any.
+    /// Groups that are not public only admit allowlisted addresses this way.
     pub fn join_group(env: Env, member: Address, group_id: u64) -> Result<(), ContractError> {
         group::join_group(&env, member, group_id)
     }
@@ -59,16 +81,144 @@ impl SoroSaveContract {
         group::leave_group(&env, member, group_id)
     }
 
+    /// Set the payout order for a group created with the `Explicit` strategy.
+    pub fn set_payout_order(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        order: Vec<Address>,
+    ) -> Result<(), ContractError> {
+        group::set_payout_order(&env, admin, group_id, order)
+    }
+
+    /// Pay each round out as soon as it is complete, whether by a contribution,
+    /// prepayments, a close or a member leaving (group admin only).
+    pub fn set_auto_payout(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        group::set_auto_payout(&env, admin, group_id, enabled)
+    }
+
+    /// Remove a member (group admin only). Anyone can be removed while the group is
+    /// forming; once active only members with a recorded default.
+    pub fn remove_member(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        member: Address,
+        reason: String,
+    ) -> Result<(), ContractError> {
+        group::remove_member(&env, admin, group_id, member, reason)
+    }
+
+    /// Require the group admin to approve seat transfers (group admin only).
+    pub fn set_transfer_approval(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        required: bool,
+    ) -> Result<(), ContractError> {
+        group::set_transfer_approval(&env, admin, group_id, required)
+    }
+
+    /// Hand a seat in an active group to a new member, who takes over its payout
+    /// position, collateral and current-round payments.
+    pub fn transfer_seat(
+        env: Env,
+        from: Address,
+        to: Address,
+        group_id: u64,
+    ) -> Result<(), ContractError> {
+        group::transfer_seat(&env, from, to, group_id)
+    }
+
+    /// Bid for an early payout position in a group created with the `Bid` strategy.
+    pub fn bid_for_position(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        group::bid_for_position(&env, member, group_id, amount)
+    }
+
+    /// Set the penalty, in basis points of what a member has paid in, for exiting an
+    /// active group (group admin only, while forming).
+    pub fn set_exit_penalty(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        exit::set_exit_penalty(&env, admin, group_id, bps)
+    }
+
+    /// Leave an active group, settling past payments against payouts received less
+    /// the exit penalty. Returns the settlement; positive means the member is owed.
+    pub fn exit_group(env: Env, member: Address, group_id: u64) -> Result<i128, ContractError> {
+        exit::exit_group(&env, member, group_id)
+    }
+
+    /// Get what a member would settle for if they exited now.
+    pub fn get_exit_settlement(
+        env: Env,
+        member: Address,
+        group_id: u64,
+    ) -> Result<i128, ContractError> {
+        exit::get_exit_settlement(&env, member, group_id)
+    }
+
+    /// Get a group's exit penalty in basis points.
+    pub fn get_exit_penalty(env: Env, group_id: u64) -> Result<u32, ContractError> {
+        exit::get_exit_penalty(&env, group_id)
+    }
+
     /// Start the group rounds. Only the group admin can call this.
     pub fn start_group(env: Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
         group::start_group(&env, admin, group_id)
     }
 
+    /// Cancel a group that has not started, refunding every deposit (group admin only).
+    pub fn cancel_group(env: Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
+        group::cancel_group(&env, admin, group_id)
+    }
+
+    /// Start a group that is due: its scheduled start time has passed, or it is full
+    /// and set to start when full. Anyone can call this.
+    pub fn try_start(env: Env, group_id: u64) -> Result<(), ContractError> {
+        group::try_start(&env, group_id)
+    }
+
+    /// Schedule a group to start at `start_at` (0 for none) and/or once its last
+    /// seat is taken (group admin only, while forming).
+    pub fn set_auto_start(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        start_at: u64,
+        when_full: bool,
+    ) -> Result<(), ContractError> {
+        group::set_auto_start(&env, admin, group_id, start_at, when_full)
+    }
+
     /// Get group details.
     pub fn get_group(env: Env, group_id: u64) -> Result<SavingsGroup, ContractError> {
         group::get_group(&env, group_id)
     }
 
+    /// Get the funds currently held in escrow for a group.
+    pub fn get_group_balance(env: Env, group_id: u64) -> Result<i128, ContractError> {
+        group::get_group_balance(&env, group_id)
+    }
+
+    /// Get the collateral a member currently has locked in a group.
+    pub fn get_collateral(env: Env, member: Address, group_id: u64) -> Result<i128, ContractError> {
+        group::get_collateral(&env, member, group_id)
+    }
+
     /// Get all group IDs a member belongs to.
     pub fn get_member_groups(env: Env, member: Address) -> Vec<u64> {
         group::get_member_groups(&env, member)
@@ -76,11 +226,49 @@ impl SoroSaveContract {
 
     // ─── Contributions ──────────────────────────────────────────────
 
-    /// Contribute to the current round of a group.
+    /// Contribute to the current round of a group, paying whatever is still owed.
     pub fn contribute(env: Env, member: Address, group_id: u64) -> Result<(), ContractError> {
         contribution::contribute(&env, member, group_id)
     }
 
+    /// Pay an installment towards the current round's contribution.
+    pub fn contribute_partial(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        contribution::contribute_partial(&env, member, group_id, amount)
+    }
+
+    /// Prepay the contributions for several upcoming rounds.
+    pub fn prepay(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        rounds: u32,
+    ) -> Result<(), ContractError> {
+        contribution::prepay(&env, member, group_id, rounds)
+    }
+
+    /// Get a member's remaining prepayment, if any.
+    pub fn get_prepayment(
+        env: Env,
+        member: Address,
+        group_id: u64,
+    ) -> Result<Option<Prepayment>, ContractError> {
+        contribution::get_prepayment(&env, member, group_id)
+    }
+
+    /// Get how much a member has paid towards the current round so far.
+    pub fn get_member_round_balance(
+        env: Env,
+        member: Address,
+        group_id: u64,
+    ) -> Result<i128, ContractError> {
+        contribution::get_member_round_balance(&env, member, group_id)
+    }
+
     /// Get the status of a specific round.
     pub fn get_round_status(
         env: Env,
@@ -90,7 +278,7 @@ impl SoroSaveContract {
         contribution::get_round_status(&env, group_id, round)
     }
 
-    /// Check if a member has contributed in a specific round.
+    /// Check if a member has paid in full in a specific round.
     pub fn has_contributed(
         env: Env,
         member: Address,
@@ -100,15 +288,266 @@ impl SoroSaveContract {
         contribution::has_contributed(&env, member, group_id, round)
     }
 
+    /// Close the current round once its deadline has passed. Members who have not
+    /// contributed are recorded as defaulters and the round pays out what was collected.
+    pub fn close_round(env: Env, group_id: u64) -> Result<(), ContractError> {
+        contribution::close_round(&env, group_id, None)
+    }
+
+    /// Get the number of rounds a member has defaulted on in a group.
+    pub fn get_default_count(
+        env: Env,
+        member: Address,
+        group_id: u64,
+    ) -> Result<u32, ContractError> {
+        contribution::get_default_count(&env, member, group_id)
+    }
+
+    // ─── Membership Access ──────────────────────────────────────────
+
+    /// Set who may join a forming group (group admin only).
+    pub fn set_visibility(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        visibility: GroupVisibility,
+    ) -> Result<(), ContractError> {
+        access::set_visibility(&env, admin, group_id, visibility)
+    }
+
+    /// Ask to join a group. Its collateral, if any, is held with the request and
+    /// returned if the request is withdrawn, rejected or still pending when the group
+    /// starts or is wound up.
+    pub fn request_join(env: Env, member: Address, group_id: u64) -> Result<(), ContractError> {
+        access::request_join(&env, member, group_id)
+    }
+
+    /// Withdraw a pending join request, returning its collateral.
+    pub fn cancel_join_request(
+        env: Env,
+        member: Address,
+        group_id: u64,
+    ) -> Result<(), ContractError> {
+        access::cancel_join_request(&env, member, group_id)
+    }
+
+    /// Admit a pending join request, or allowlist an address that has not asked
+    /// yet (group admin only).
+    pub fn approve_join(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        access::approve_join(&env, admin, group_id, member)
+    }
+
+    /// Turn down a pending join request and return its collateral (group admin only).
+    pub fn reject_join(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        access::reject_join(&env, admin, group_id, member)
+    }
+
+    /// Get a group's pending join requests.
+    pub fn get_join_requests(env: Env, group_id: u64) -> Result<Vec<Address>, ContractError> {
+        access::get_join_requests(&env, group_id)
+    }
+
+    /// Remove an address from the group's allowlist (group admin only).
+    pub fn revoke_join(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        access::revoke_join(&env, admin, group_id, member)
+    }
+
+    /// Publish the SHA-256 hash of the group's invite code (group admin only).
+    pub fn set_invite_code(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        code_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        access::set_invite_code(&env, admin, group_id, code_hash)
+    }
+
+    /// Join a group by presenting its invite code. Only while the group's visibility
+    /// is `InviteCode`.
+    pub fn join_group_with_code(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        code: Bytes,
+    ) -> Result<(), ContractError> {
+        access::join_group_with_code(&env, member, group_id, code)
+    }
+
+    /// Get the addresses allowlisted for a group.
+    pub fn get_allowlist(env: Env, group_id: u64) -> Result<Vec<Address>, ContractError> {
+        access::get_allowlist(&env, group_id)
+    }
+
+    // ─── Accepted Tokens ────────────────────────────────────────────
+
+    /// Accept another token for contributions at a fixed rate (group admin only, while forming).
+    /// `rate` is the value of one unit in the payout token, scaled by 10^7.
+    pub fn add_accepted_token(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        token: Address,
+        rate: i128,
+    ) -> Result<(), ContractError> {
+        asset::add_accepted_token(&env, admin, group_id, token, rate)
+    }
+
+    /// Stop accepting a token for contributions (group admin only, while forming).
+    pub fn remove_accepted_token(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        token: Address,
+    ) -> Result<(), ContractError> {
+        asset::remove_accepted_token(&env, admin, group_id, token)
+    }
+
+    /// Choose the token a member contributes with (while forming).
+    pub fn set_contribution_token(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        token: Address,
+    ) -> Result<(), ContractError> {
+        asset::set_contribution_token(&env, member, group_id, token)
+    }
+
+    /// Get the extra tokens a group accepts and their rates.
+    pub fn get_accepted_tokens(
+        env: Env,
+        group_id: u64,
+    ) -> Result<Map<Address, i128>, ContractError> {
+        asset::get_accepted_tokens(&env, group_id)
+    }
+
+    /// Get the token a member contributes with.
+    pub fn get_contribution_token(
+        env: Env,
+        member: Address,
+        group_id: u64,
+    ) -> Result<Address, ContractError> {
+        asset::get_contribution_token(&env, member, group_id)
+    }
+
+    /// Get how much of a token the group holds in escrow.
+    pub fn get_asset_balance(
+        env: Env,
+        group_id: u64,
+        token: Address,
+    ) -> Result<i128, ContractError> {
+        asset::get_asset_balance(&env, group_id, token)
+    }
+
+    /// Denominate contributions in a SEP-40 oracle's reference currency, or clear it
+    /// (group admin only, while forming). Every contribution token is then priced
+    /// by the oracle at the time of payment.
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        oracle: Option<Address>,
+    ) -> Result<(), ContractError> {
+        oracle::set_price_oracle(&env, admin, group_id, oracle)
+    }
+
+    /// Get the price oracle a group is denominated through, if any.
+    pub fn get_price_oracle(env: Env, group_id: u64) -> Result<Option<Address>, ContractError> {
+        oracle::get_price_oracle(&env, group_id)
+    }
+
     // ─── Payouts ────────────────────────────────────────────────────
 
     /// Distribute the pot to the current round's recipient. Anyone can call this
-    /// once all contributions are in.
+    /// once all contributions are in or the round has been closed.
     pub fn distribute_payout(env: Env, group_id: u64) -> Result<(), ContractError> {
-        payout::distribute_payout(&env, group_id)
+        payout::distribute_payout(&env, group_id, None)
+    }
+
+    // ─── Keepers ────────────────────────────────────────────────────
+
+    /// `distribute_payout`, paying `keeper` the keeper share of the protocol fee and
+    /// the group's keeper reward.
+    pub fn distribute_payout_as_keeper(
+        env: Env,
+        group_id: u64,
+        keeper: Address,
+    ) -> Result<(), ContractError> {
+        payout::distribute_payout(&env, group_id, Some(&keeper))
+    }
+
+    /// `close_round`, paying `keeper` the group's keeper reward.
+    pub fn close_round_as_keeper(
+        env: Env,
+        group_id: u64,
+        keeper: Address,
+    ) -> Result<(), ContractError> {
+        contribution::close_round(&env, group_id, Some(&keeper))
+    }
+
+    /// Add funds, in the group's token, to the pool that rewards keepers. Whatever is
+    /// left when the group ends goes back to the funders in proportion to what they put in.
+    pub fn fund_keeper_pool(
+        env: Env,
+        funder: Address,
+        group_id: u64,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        keeper::fund_keeper_pool(&env, funder, group_id, amount)
+    }
+
+    /// Set the reward the keeper pool pays per payout or round close (group admin only).
+    pub fn set_keeper_reward(
+        env: Env,
+        admin: Address,
+        group_id: u64,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        keeper::set_keeper_reward(&env, admin, group_id, amount)
+    }
+
+    /// Get the balance of a group's keeper pool.
+    pub fn get_keeper_pool(env: Env, group_id: u64) -> Result<i128, ContractError> {
+        keeper::get_keeper_pool(&env, group_id)
+    }
+
+    /// Get the reward a group pays keepers per call.
+    pub fn get_keeper_reward(env: Env, group_id: u64) -> Result<i128, ContractError> {
+        keeper::get_keeper_reward(&env, group_id)
     }
 
-    /// Get the payout order for a group.
+    /// Bid in the current round's auction. The bid is the amount the member will accept
+    /// instead of the full pot; the lowest bid wins.
+    pub fn place_bid(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        payout::place_bid(&env, member, group_id, amount)
+    }
+
+    /// Settle the current round's auction. Anyone can call this once the deadline has
+    /// passed or every unpaid member has bid.
+    pub fn settle_auction(env: Env, group_id: u64) -> Result<(), ContractError> {
+        payout::settle_auction(&env, group_id)
+    }
+
+    /// Get the payout order for a group. For auction groups this lists the winners so far.
     pub fn get_payout_order(env: Env, group_id: u64) -> Result<Vec<Address>, ContractError> {
         payout::get_payout_order(&env, group_id)
     }
@@ -118,6 +557,62 @@ impl SoroSaveContract {
         payout::get_current_recipient(&env, group_id)
     }
 
+    // ─── Protocol Fees ──────────────────────────────────────────────
+
+    /// Set the protocol treasury and default fee rates in basis points (protocol admin only).
+    pub fn set_fee_config(
+        env: Env,
+        admin: Address,
+        treasury: Address,
+        payout_bps: u32,
+        contribution_bps: u32,
+    ) -> Result<(), ContractError> {
+        fee::set_fee_config(&env, admin, treasury, payout_bps, contribution_bps)
+    }
+
+    /// Override the fee rates for groups using a specific token (protocol admin only).
+    pub fn set_token_fee(
+        env: Env,
+        admin: Address,
+        token: Address,
+        payout_bps: u32,
+        contribution_bps: u32,
+    ) -> Result<(), ContractError> {
+        fee::set_token_fee(&env, admin, token, payout_bps, contribution_bps)
+    }
+
+    /// Remove a per-token fee override (protocol admin only).
+    pub fn remove_token_fee(env: Env, admin: Address, token: Address) -> Result<(), ContractError> {
+        fee::remove_token_fee(&env, admin, token)
+    }
+
+    /// Set the share of payout fees, in basis points, paid to keepers (protocol admin only).
+    pub fn set_keeper_fee_share(env: Env, admin: Address, bps: u32) -> Result<(), ContractError> {
+        fee::set_keeper_fee_share(&env, admin, bps)
+    }
+
+    /// Get the share of payout fees paid to keepers.
+    pub fn get_keeper_fee_share(env: Env) -> u32 {
+        fee::get_keeper_fee_share(&env)
+    }
+
+    /// Get the protocol fee configuration.
+    pub fn get_fee_config(env: Env) -> Result<FeeConfig, ContractError> {
+        fee::get_fee_config(&env)
+    }
+
+    /// Get the fee rates that apply to a token.
+    pub fn get_fee_rates(env: Env, token: Address) -> FeeRates {
+        fee::get_fee_rates(&env, token)
+    }
+
+    // ─── Reputation ─────────────────────────────────────────────────
+
+    /// Get a member's payment history and reputation score.
+    pub fn get_reputation(env: Env, member: Address) -> Reputation {
+        reputation::get_reputation(&env, member)
+    }
+
     // ─── Admin / Governance ─────────────────────────────────────────
 
     /// Pause an active group.
@@ -130,22 +625,62 @@ impl SoroSaveContract {
         admin::resume_group(&env, admin, group_id)
     }
 
-    /// Raise a dispute on a group.
+    /// Raise a dispute on a group, freezing it and opening a member vote on the
+    /// proposed outcome.
     pub fn raise_dispute(
         env: Env,
         member: Address,
         group_id: u64,
         reason: String,
+        proposed: DisputeOutcome,
     ) -> Result<(), ContractError> {
-        admin::raise_dispute(&env, member, group_id, reason)
+        admin::raise_dispute(&env, member, group_id, reason, proposed)
     }
 
-    /// Resolve a dispute (group admin or protocol admin).
+    /// Vote on a group's open dispute. An outcome backed by a majority of all members
+    /// is executed immediately.
+    pub fn vote_dispute(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        outcome: DisputeOutcome,
+    ) -> Result<(), ContractError> {
+        admin::vote_dispute(&env, member, group_id, outcome)
+    }
+
+    /// Execute the winning outcome once the voting period is over. Anyone can call this.
+    pub fn finalize_dispute(env: Env, group_id: u64) -> Result<(), ContractError> {
+        admin::finalize_dispute(&env, group_id)
+    }
+
+    /// Dismiss a dispute without a vote (protocol admin only).
     pub fn resolve_dispute(env: Env, admin: Address, group_id: u64) -> Result<(), ContractError> {
         admin::resolve_dispute(&env, admin, group_id)
     }
 
-    /// Emergency withdraw — distribute remaining funds proportionally to all members.
+    /// Attach evidence (a content hash or URI) to an unresolved dispute.
+    pub fn add_evidence(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        dispute_id: u32,
+        evidence: String,
+    ) -> Result<(), ContractError> {
+        admin::add_evidence(&env, member, group_id, dispute_id, evidence)
+    }
+
+    /// Get a dispute raised on a group, including its votes and resolution.
+    pub fn get_dispute(env: Env, group_id: u64, dispute_id: u32) -> Result<Dispute, ContractError> {
+        admin::get_dispute(&env, group_id, dispute_id)
+    }
+
+    /// Get every dispute ever raised on a group, oldest first.
+    pub fn list_disputes(env: Env, group_id: u64) -> Result<Vec<Dispute>, ContractError> {
+        admin::list_disputes(&env, group_id)
+    }
+
+    /// Emergency withdraw — refund each member's contribution to the unfinished round
+    /// and split any remaining escrow equally.
     pub fn emergency_withdraw(
         env: Env,
         admin: Address,
@@ -154,7 +689,7 @@ impl SoroSaveContract {
         admin::emergency_withdraw(&env, admin, group_id)
     }
 
-    /// Transfer group admin role.
+    /// Propose a new group admin. Takes effect once they call `accept_group_admin`.
     pub fn set_group_admin(
         env: Env,
         current_admin: Address,
@@ -163,6 +698,69 @@ impl SoroSaveContract {
     ) -> Result<(), ContractError> {
         admin::set_group_admin(&env, current_admin, group_id, new_admin)
     }
+
+    /// Accept a pending group admin proposal.
+    pub fn accept_group_admin(
+        env: Env,
+        new_admin: Address,
+        group_id: u64,
+    ) -> Result<(), ContractError> {
+        admin::accept_group_admin(&env, new_admin, group_id)
+    }
+
+    /// Propose a new protocol admin. Takes effect once they call `accept_protocol_admin`.
+    pub fn propose_protocol_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), ContractError> {
+        admin::propose_protocol_admin(&env, admin, new_admin)
+    }
+
+    /// Accept a pending protocol admin proposal.
+    pub fn accept_protocol_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        admin::accept_protocol_admin(&env, new_admin)
+    }
+}
+
+// `contractimpl` emits argument helpers next to the impl block rather than
+// inside it, so the lint is allowed on a module holding just this entry point.
+#[allow(clippy::too_many_arguments)]
+mod create {
+    use super::*;
+
+    #[contractimpl]
+    impl SoroSaveContract {
+        /// Create a new savings group. The caller becomes the group admin and first member.
+        /// A non-zero `collateral` is locked from every member, including the admin, on joining.
+        /// `payout_strategy` decides how the payout order is built when the group starts.
+        /// Members need a reputation score of at least `min_reputation` to join.
+        pub fn create_group(
+            env: Env,
+            admin: Address,
+            name: String,
+            token: Address,
+            contribution_amount: i128,
+            cycle_length: u64,
+            max_members: u32,
+            collateral: i128,
+            payout_strategy: PayoutOrderStrategy,
+            min_reputation: u32,
+        ) -> Result<u64, ContractError> {
+            group::create_group(
+                &env,
+                admin,
+                name,
+                token,
+                contribution_amount,
+                cycle_length,
+                max_members,
+                collateral,
+                payout_strategy,
+                min_reputation,
+            )
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,90 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+use crate::errors::ContractError;
+use crate::storage;
+use crate::types::{GroupStatus, PayoutOrderStrategy};
+
+/// Prices older than this are treated as unavailable.
+pub const MAX_PRICE_AGE: u64 = 15 * 60;
+
+/// Asset identifier used by SEP-40 price feeds.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// A single SEP-40 price observation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// The subset of the SEP-40 oracle interface the contract relies on.
+#[contractclient(name = "PriceOracleClient")]
+#[allow(dead_code)] // only the generated client is used
+pub trait PriceOracle {
+    /// Number of decimals in reported prices.
+    fn decimals(env: Env) -> u32;
+
+    /// Most recent price of `asset` in the oracle's base currency.
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+/// Denominate a group's contributions in the oracle's reference currency, or
+/// clear the oracle to go back to the payout token. Only while forming.
+pub fn set_price_oracle(
+    env: &Env,
+    admin: Address,
+    group_id: u64,
+    oracle: Option<Address>,
+) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if admin != group.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if group.status != GroupStatus::Forming {
+        return Err(ContractError::GroupNotForming);
+    }
+
+    // Auction bids are made against a pot counted in the payout token
+    if group.payout_strategy == PayoutOrderStrategy::Auction {
+        return Err(ContractError::MultiAssetNotSupported);
+    }
+
+    match &oracle {
+        Some(oracle) => storage::set_price_oracle(env, group_id, oracle),
+        None => storage::remove_price_oracle(env, group_id),
+    }
+
+    env.events()
+        .publish((crate::symbol_short!("oracle"),), (group_id, oracle));
+
+    Ok(())
+}
+
+pub fn get_price_oracle(env: &Env, group_id: u64) -> Result<Option<Address>, ContractError> {
+    storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+    Ok(storage::get_price_oracle(env, group_id))
+}
+
+/// The latest fresh price of `token` and the scale it is expressed in.
+pub fn price(env: &Env, oracle: &Address, token: &Address) -> Result<(i128, i128), ContractError> {
+    let client = PriceOracleClient::new(env, oracle);
+    let data = client
+        .lastprice(&Asset::Stellar(token.clone()))
+        .ok_or(ContractError::PriceUnavailable)?;
+
+    if data.price <= 0 || data.timestamp + MAX_PRICE_AGE < env.ledger().timestamp() {
+        return Err(ContractError::PriceUnavailable);
+    }
+
+    Ok((data.price, 10i128.pow(client.decimals())))
+}
@@ -1,10 +1,23 @@
 use soroban_sdk::{Address, Env, Map, Vec};
 
+use crate::asset;
+use crate::contribution;
 use crate::errors::ContractError;
+use crate::exit;
+use crate::fee;
+use crate::group;
+use crate::keeper;
+use crate::reputation;
 use crate::storage;
-use crate::types::{GroupStatus, RoundInfo};
+use crate::types::{GroupStatus, PayoutOrderStrategy, RoundInfo, SavingsGroup};
 
-pub fn distribute_payout(env: &Env, group_id: u64) -> Result<(), ContractError> {
+/// Pay the current round's pot out and open the next round. A keeper, if given,
+/// is rewarded for the call.
+pub fn distribute_payout(
+    env: &Env,
+    group_id: u64,
+    keeper: Option<&Address>,
+) -> Result<(), ContractError> {
     let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
 
     if group.status != GroupStatus::Active {
@@ -18,42 +31,138 @@ pub fn distribute_payout(env: &Env, group_id: u64) -> Result<(), ContractError>
         return Err(ContractError::RoundNotComplete);
     }
 
-    // Transfer the pot to the round's recipient
+    if !is_round_settled(&group) {
+        return Err(ContractError::AuctionNotSettled);
+    }
+
+    // Position bid premiums go to the member paid last
+    let is_final_round = group.current_round >= group.total_rounds;
+    let mut pot = round_info
+        .asset_totals
+        .get(group.token.clone())
+        .unwrap_or(0);
+    if is_final_round {
+        pot += storage::get_bid_pool(env, group_id);
+        storage::remove_bid_pool(env, group_id);
+    }
+
+    // Exit settlements are spread over the remaining rounds
+    pot += exit::release_reserve(env, &group);
+    let escrowed = pot;
+    pot -= exit::pay_claims(env, &group, pot);
+
+    // An auction winner's discount is shared equally among everyone else. The
+    // indivisible remainder stays with the winner.
+    let discount = round_info.discount.clamp(0, pot);
+    let others = group.members.len() as i128 - 1;
+    let share = if others > 0 { discount / others } else { 0 };
+    let mut amount = pot - share * others;
+
     let token_client = soroban_sdk::token::Client::new(env, &group.token);
-    token_client.transfer(
-        &env.current_contract_address(),
-        &round_info.recipient,
-        &round_info.total_contributed,
+    let contract_addr = env.current_contract_address();
+
+    // The protocol fee comes out of the recipient's share, less the keeper's cut
+    let protocol_fee = fee::payout_fee(env, &group.token, amount);
+    let keeper_cut = match keeper {
+        Some(_) => fee::keeper_share(env, protocol_fee),
+        None => 0,
+    };
+    fee::collect(
+        env,
+        group_id,
+        &group.token,
+        &contract_addr,
+        protocol_fee - keeper_cut,
+    );
+    amount -= protocol_fee;
+    if share > 0 {
+        for member in group.members.iter() {
+            if member != round_info.recipient {
+                token_client.transfer(&contract_addr, &member, &share);
+            }
+        }
+
+        env.events().publish(
+            (crate::symbol_short!("dividend"),),
+            (group_id, round_info.round_number, share),
+        );
+    }
+
+    // Transfer the pot to the round's recipient. A round closed after its
+    // deadline may have collected nothing.
+    if amount > 0 {
+        token_client.transfer(&contract_addr, &round_info.recipient, &amount);
+    }
+    storage::set_group_balance(
+        env,
+        group_id,
+        storage::get_group_balance(env, group_id) - escrowed,
     );
 
     env.events().publish(
         (crate::symbol_short!("payout"),),
-        (
-            group_id,
-            round_info.recipient.clone(),
-            round_info.total_contributed,
-        ),
+        (group_id, round_info.recipient.clone(), amount),
     );
 
+    // Contributions made in other accepted tokens are paid out in kind
+    for (token, total) in round_info.asset_totals.iter() {
+        if token == group.token || total <= 0 {
+            continue;
+        }
+
+        let protocol_fee = fee::payout_fee(env, &token, total);
+        fee::collect(env, group_id, &token, &contract_addr, protocol_fee);
+        let amount = total - protocol_fee;
+        soroban_sdk::token::Client::new(env, &token).transfer(
+            &contract_addr,
+            &round_info.recipient,
+            &amount,
+        );
+        asset::adjust_escrow(env, &group, &token, -total);
+
+        env.events().publish(
+            (crate::symbol_short!("payout_as"),),
+            (group_id, round_info.recipient.clone(), token, amount),
+        );
+    }
+
+    if let Some(keeper) = keeper {
+        keeper::reward(env, &group, keeper, keeper_cut);
+    }
+
     // Advance to next round or complete the group
-    if group.current_round >= group.total_rounds {
+    if is_final_round {
         group.status = GroupStatus::Completed;
         storage::set_group(env, &group);
 
+        // Every obligation has been met, so collateral goes back to its owners
+        for member in group.members.iter() {
+            group::release_collateral(env, &group, &member);
+            contribution::refund_prepayment(env, &group, &member);
+            reputation::record_group_completed(env, &member);
+        }
+        exit::settle_claims(env, &group);
+        keeper::refund_pool(env, &group);
+
         env.events()
             .publish((crate::symbol_short!("grp_comp"),), group_id);
     } else {
         group.current_round += 1;
-        let next_recipient = group.payout_order.get(group.current_round - 1).unwrap();
+        let next_recipient = scheduled_recipient(&group);
 
-        let new_round = RoundInfo {
+        let mut new_round = RoundInfo {
             round_number: group.current_round,
             recipient: next_recipient,
             contributions: Map::new(env),
             total_contributed: 0,
             is_complete: false,
             deadline: env.ledger().timestamp() + group.cycle_length,
+            defaulters: Vec::new(env),
+            discount: 0,
+            asset_totals: Map::new(env),
+            payments: Map::new(env),
         };
+        contribution::apply_prepayments(env, &group, &mut new_round);
 
         storage::set_round(env, group_id, &new_round);
         storage::set_group(env, &group);
@@ -62,11 +171,174 @@ pub fn distribute_payout(env: &Env, group_id: u64) -> Result<(), ContractError>
             (crate::symbol_short!("rnd_new"),),
             (group_id, group.current_round),
         );
+
+        // Prepayments alone may have filled the new round
+        auto_payout(env, group_id);
+    }
+
+    Ok(())
+}
+
+/// Pay the current round out if the group has auto-payout on and the round is
+/// complete and settled. Called wherever a round can be completed; an unsettled
+/// auction waits for `settle_auction`.
+pub fn auto_payout(env: &Env, group_id: u64) {
+    let Some(group) = storage::get_group(env, group_id) else {
+        return;
+    };
+    if group.status != GroupStatus::Active || !group.auto_payout || !is_round_settled(&group) {
+        return;
+    }
+
+    let complete = storage::get_round(env, group_id, group.current_round)
+        .is_some_and(|round_info| round_info.is_complete);
+    if complete && distribute_payout(env, group_id, None).is_err() {
+        env.events()
+            .publish((crate::symbol_short!("pay_fail"),), group_id);
+    }
+}
+
+/// Place or update a bid in the current round's auction. The bid is the amount the
+/// member will accept instead of the full pot; the lowest bid wins and the
+/// difference is shared among the other members.
+pub fn place_bid(
+    env: &Env,
+    member: Address,
+    group_id: u64,
+    amount: i128,
+) -> Result<(), ContractError> {
+    member.require_auth();
+
+    let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    if group.payout_strategy != PayoutOrderStrategy::Auction {
+        return Err(ContractError::NotAuctionGroup);
+    }
+
+    if !group.members.contains(&member) {
+        return Err(ContractError::NotMember);
     }
 
+    if group.payout_order.contains(&member) {
+        return Err(ContractError::AlreadyPaidOut);
+    }
+
+    let round_info = storage::get_round(env, group_id, group.current_round)
+        .ok_or(ContractError::RoundNotActive)?;
+
+    // Bidding closes at the round deadline or once the auction is settled
+    if is_round_settled(&group) || env.ledger().timestamp() > round_info.deadline {
+        return Err(ContractError::BiddingClosed);
+    }
+
+    if amount <= 0 || amount > full_pot(&group) {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let mut bids = storage::get_auction_bids(env, group_id, group.current_round);
+    bids.set(member.clone(), amount);
+    storage::set_auction_bids(env, group_id, group.current_round, &bids);
+
+    env.events().publish(
+        (crate::symbol_short!("auc_bid"),),
+        (group_id, group.current_round, member, amount),
+    );
+
     Ok(())
 }
 
+/// Settle the current round's auction. Anyone can call this once the deadline has
+/// passed or every unpaid member has bid. Ties go to the earliest joiner; with no
+/// bids the next unpaid member in join order takes the full pot.
+pub fn settle_auction(env: &Env, group_id: u64) -> Result<(), ContractError> {
+    let mut group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
+
+    if group.status != GroupStatus::Active {
+        return Err(ContractError::GroupNotActive);
+    }
+
+    if group.payout_strategy != PayoutOrderStrategy::Auction {
+        return Err(ContractError::NotAuctionGroup);
+    }
+
+    if is_round_settled(&group) {
+        return Err(ContractError::BiddingClosed);
+    }
+
+    let mut round_info = storage::get_round(env, group_id, group.current_round)
+        .ok_or(ContractError::RoundNotActive)?;
+    let bids = storage::get_auction_bids(env, group_id, group.current_round);
+
+    let mut winner: Option<(Address, i128)> = None;
+    let mut eligible = 0;
+    let mut all_bid = true;
+    for m in group.members.iter() {
+        if group.payout_order.contains(&m) {
+            continue;
+        }
+        eligible += 1;
+        match bids.get(m.clone()) {
+            Some(bid) => {
+                if winner.as_ref().is_none_or(|(_, best)| bid < *best) {
+                    winner = Some((m, bid));
+                }
+            }
+            None => all_bid = false,
+        }
+    }
+
+    // The last unpaid member has no one to bid against
+    if !all_bid && eligible > 1 && env.ledger().timestamp() <= round_info.deadline {
+        return Err(ContractError::DeadlineNotReached);
+    }
+
+    // Members may have left since bidding, shrinking the pot below the bid
+    let pot = full_pot(&group);
+    let (recipient, bid) = winner.unwrap_or((round_info.recipient.clone(), pot));
+    round_info.recipient = recipient.clone();
+    round_info.discount = (pot - bid).max(0);
+    group.payout_order.push_back(recipient.clone());
+
+    storage::set_round(env, group_id, &round_info);
+    storage::set_group(env, &group);
+
+    env.events().publish(
+        (crate::symbol_short!("auc_setl"),),
+        (group_id, group.current_round, recipient, bid),
+    );
+
+    auto_payout(env, group_id);
+
+    Ok(())
+}
+
+/// The member in line to receive the current round's pot. Auction rounds default
+/// to the first unpaid member in join order until the auction settles.
+pub fn scheduled_recipient(group: &SavingsGroup) -> Address {
+    if group.payout_strategy == PayoutOrderStrategy::Auction {
+        for m in group.members.iter() {
+            if !group.payout_order.contains(&m) {
+                return m;
+            }
+        }
+    }
+    group.payout_order.get(group.current_round - 1).unwrap()
+}
+
+/// Whether the current round's recipient is settled. Only auction rounds wait.
+pub fn is_round_settled(group: &SavingsGroup) -> bool {
+    group.payout_strategy != PayoutOrderStrategy::Auction
+        || group.payout_order.len() >= group.current_round
+}
+
+fn full_pot(group: &SavingsGroup) -> i128 {
+    group.contribution_amount * group.members.len() as i128
+}
+
 pub fn get_payout_order(env: &Env, group_id: u64) -> Result<Vec<Address>, ContractError> {
     let group = storage::get_group(env, group_id).ok_or(ContractError::GroupNotFound)?;
     Ok(group.payout_order)
@@ -0,0 +1,55 @@
+use soroban_sdk::{Address, Env};
+
+use crate::storage;
+use crate::types::Reputation;
+
+/// Score of a member with no history. A fresh address must not outrank one
+/// that has defaulted, so it starts at the bottom.
+pub const BASE_SCORE: u32 = 0;
+pub const MAX_SCORE: u32 = 1_000;
+
+/// A member's reputation across every group they have been part of.
+pub fn get_reputation(env: &Env, member: Address) -> Reputation {
+    storage::get_reputation(env, &member).unwrap_or(Reputation {
+        rounds_on_time: 0,
+        late_payments: 0,
+        defaults: 0,
+        groups_completed: 0,
+        score: BASE_SCORE,
+    })
+}
+
+/// Record a round's contribution paid in full, on time or late.
+pub fn record_payment(env: &Env, member: &Address, on_time: bool) {
+    update(env, member, |rep| {
+        if on_time {
+            rep.rounds_on_time += 1;
+        } else {
+            rep.late_payments += 1;
+        }
+    });
+}
+
+pub fn record_default(env: &Env, member: &Address) {
+    update(env, member, |rep| rep.defaults += 1);
+}
+
+pub fn record_group_completed(env: &Env, member: &Address) {
+    update(env, member, |rep| rep.groups_completed += 1);
+}
+
+fn update(env: &Env, member: &Address, f: impl FnOnce(&mut Reputation)) {
+    let mut rep = get_reputation(env, member.clone());
+    f(&mut rep);
+    rep.score = score(&rep);
+    storage::set_reputation(env, member, &rep);
+}
+
+/// Defaults weigh heaviest, then late payments; finishing whole groups counts
+/// for more than individual rounds.
+fn score(rep: &Reputation) -> u32 {
+    let credit =
+        BASE_SCORE as i64 + 10 * rep.rounds_on_time as i64 + 50 * rep.groups_completed as i64;
+    let debit = 25 * rep.late_payments as i64 + 100 * rep.defaults as i64;
+    (credit - debit).clamp(0, MAX_SCORE as i64) as u32
+}
@@ -1,6 +1,8 @@
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, Map, Vec};
 
-use crate::types::{DataKey, Dispute, RoundInfo, SavingsGroup};
+use crate::types::{
+    DataKey, Dispute, FeeConfig, FeeRates, Prepayment, Reputation, RoundInfo, SavingsGroup,
+};
 
 const INSTANCE_TTL_THRESHOLD: u32 = 100;
 const INSTANCE_TTL_EXTEND: u32 = 500;
@@ -22,6 +24,96 @@ pub fn has_admin(env: &Env) -> bool {
     env.storage().instance().has(&DataKey::Admin)
 }
 
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::PendingAdmin, admin);
+    extend_instance_ttl(env);
+}
+
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+}
+
+// --- Schema Version ---
+
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(0)
+}
+
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &version);
+    extend_instance_ttl(env);
+}
+
+/// The next group id a batched migration will rewrite.
+pub fn get_migration_cursor(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MigrationCursor)
+        .unwrap_or(1)
+}
+
+pub fn set_migration_cursor(env: &Env, group_id: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MigrationCursor, &group_id);
+    extend_instance_ttl(env);
+}
+
+pub fn remove_migration_cursor(env: &Env) {
+    env.storage().instance().remove(&DataKey::MigrationCursor);
+}
+
+// --- Fees ---
+
+pub fn get_fee_config(env: &Env) -> Option<FeeConfig> {
+    env.storage().instance().get(&DataKey::FeeConfig)
+}
+
+pub fn set_fee_config(env: &Env, config: &FeeConfig) {
+    env.storage().instance().set(&DataKey::FeeConfig, config);
+    extend_instance_ttl(env);
+}
+
+pub fn get_token_fee(env: &Env, token: &Address) -> Option<FeeRates> {
+    env.storage()
+        .instance()
+        .get(&DataKey::TokenFee(token.clone()))
+}
+
+pub fn set_token_fee(env: &Env, token: &Address, rates: &FeeRates) {
+    env.storage()
+        .instance()
+        .set(&DataKey::TokenFee(token.clone()), rates);
+    extend_instance_ttl(env);
+}
+
+pub fn remove_token_fee(env: &Env, token: &Address) {
+    env.storage()
+        .instance()
+        .remove(&DataKey::TokenFee(token.clone()));
+}
+
+pub fn get_keeper_fee_share(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::KeeperFeeShare)
+        .unwrap_or(0)
+}
+
+pub fn set_keeper_fee_share(env: &Env, bps: u32) {
+    env.storage().instance().set(&DataKey::KeeperFeeShare, &bps);
+    extend_instance_ttl(env);
+}
+
 // --- Group Counter ---
 
 pub fn get_group_counter(env: &Env) -> u64 {
@@ -55,6 +147,311 @@ pub fn set_group(env: &Env, group: &SavingsGroup) {
     extend_persistent_ttl(env, &key);
 }
 
+pub fn get_pending_group_admin(env: &Env, group_id: u64) -> Option<Address> {
+    let key = DataKey::PendingGroupAdmin(group_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_pending_group_admin(env: &Env, group_id: u64, admin: &Address) {
+    let key = DataKey::PendingGroupAdmin(group_id);
+    env.storage().persistent().set(&key, admin);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_pending_group_admin(env: &Env, group_id: u64) {
+    let key = DataKey::PendingGroupAdmin(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+// --- Group Balance ---
+
+pub fn get_group_balance(env: &Env, group_id: u64) -> i128 {
+    let key = DataKey::GroupBalance(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_group_balance(env: &Env, group_id: u64, balance: i128) {
+    let key = DataKey::GroupBalance(group_id);
+    env.storage().persistent().set(&key, &balance);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_asset_balance(env: &Env, group_id: u64, token: &Address) -> i128 {
+    let key = DataKey::AssetBalance(group_id, token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_asset_balance(env: &Env, group_id: u64, token: &Address, balance: i128) {
+    let key = DataKey::AssetBalance(group_id, token.clone());
+    env.storage().persistent().set(&key, &balance);
+    extend_persistent_ttl(env, &key);
+}
+
+// --- Access ---
+
+pub fn get_allowlist(env: &Env, group_id: u64) -> Vec<Address> {
+    let key = DataKey::Allowlist(group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_allowlist(env: &Env, group_id: u64, allowlist: &Vec<Address>) {
+    let key = DataKey::Allowlist(group_id);
+    env.storage().persistent().set(&key, allowlist);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_join_requests(env: &Env, group_id: u64) -> Vec<Address> {
+    let key = DataKey::JoinRequests(group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_join_requests(env: &Env, group_id: u64, requests: &Vec<Address>) {
+    let key = DataKey::JoinRequests(group_id);
+    env.storage().persistent().set(&key, requests);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_join_requests(env: &Env, group_id: u64) {
+    let key = DataKey::JoinRequests(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn get_invite_code(env: &Env, group_id: u64) -> Option<BytesN<32>> {
+    let key = DataKey::InviteCode(group_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_invite_code(env: &Env, group_id: u64, code_hash: &BytesN<32>) {
+    let key = DataKey::InviteCode(group_id);
+    env.storage().persistent().set(&key, code_hash);
+    extend_persistent_ttl(env, &key);
+}
+
+// --- Accepted Tokens ---
+
+pub fn get_accepted_tokens(env: &Env, group_id: u64) -> Map<Address, i128> {
+    let key = DataKey::AcceptedTokens(group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Map::new(env))
+}
+
+pub fn set_accepted_tokens(env: &Env, group_id: u64, tokens: &Map<Address, i128>) {
+    let key = DataKey::AcceptedTokens(group_id);
+    env.storage().persistent().set(&key, tokens);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_member_token(env: &Env, group_id: u64, member: &Address) -> Option<Address> {
+    let key = DataKey::MemberToken(group_id, member.clone());
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_member_token(env: &Env, group_id: u64, member: &Address, token: &Address) {
+    let key = DataKey::MemberToken(group_id, member.clone());
+    env.storage().persistent().set(&key, token);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_member_token(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::MemberToken(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// --- Price Oracle ---
+
+pub fn get_price_oracle(env: &Env, group_id: u64) -> Option<Address> {
+    let key = DataKey::PriceOracle(group_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_price_oracle(env: &Env, group_id: u64, oracle: &Address) {
+    let key = DataKey::PriceOracle(group_id);
+    env.storage().persistent().set(&key, oracle);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_price_oracle(env: &Env, group_id: u64) {
+    let key = DataKey::PriceOracle(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+// --- Prepayments ---
+
+pub fn get_prepayment(env: &Env, group_id: u64, member: &Address) -> Option<Prepayment> {
+    let key = DataKey::Prepayment(group_id, member.clone());
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_prepayment(env: &Env, group_id: u64, member: &Address, prepayment: &Prepayment) {
+    let key = DataKey::Prepayment(group_id, member.clone());
+    env.storage().persistent().set(&key, prepayment);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_prepayment(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::Prepayment(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// --- Keeper Pool ---
+
+pub fn get_keeper_pool(env: &Env, group_id: u64) -> i128 {
+    let key = DataKey::KeeperPool(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_keeper_pool(env: &Env, group_id: u64, amount: i128) {
+    let key = DataKey::KeeperPool(group_id);
+    env.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_keeper_pool(env: &Env, group_id: u64) {
+    let key = DataKey::KeeperPool(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn get_keeper_funders(env: &Env, group_id: u64) -> Map<Address, i128> {
+    let key = DataKey::KeeperFunders(group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Map::new(env))
+}
+
+pub fn set_keeper_funders(env: &Env, group_id: u64, funders: &Map<Address, i128>) {
+    let key = DataKey::KeeperFunders(group_id);
+    env.storage().persistent().set(&key, funders);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_keeper_funders(env: &Env, group_id: u64) {
+    let key = DataKey::KeeperFunders(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn get_keeper_reward(env: &Env, group_id: u64) -> i128 {
+    let key = DataKey::KeeperReward(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_keeper_reward(env: &Env, group_id: u64, amount: i128) {
+    let key = DataKey::KeeperReward(group_id);
+    env.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(env, &key);
+}
+
+// --- Exits ---
+
+pub fn get_exit_penalty(env: &Env, group_id: u64) -> u32 {
+    let key = DataKey::ExitPenalty(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_exit_penalty(env: &Env, group_id: u64, bps: u32) {
+    let key = DataKey::ExitPenalty(group_id);
+    env.storage().persistent().set(&key, &bps);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_exit_reserve(env: &Env, group_id: u64) -> i128 {
+    let key = DataKey::ExitReserve(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_exit_reserve(env: &Env, group_id: u64, amount: i128) {
+    let key = DataKey::ExitReserve(group_id);
+    env.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_exit_reserve(env: &Env, group_id: u64) {
+    let key = DataKey::ExitReserve(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn get_exit_claims(env: &Env, group_id: u64) -> Map<Address, i128> {
+    let key = DataKey::ExitClaims(group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Map::new(env))
+}
+
+pub fn set_exit_claims(env: &Env, group_id: u64, claims: &Map<Address, i128>) {
+    let key = DataKey::ExitClaims(group_id);
+    env.storage().persistent().set(&key, claims);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_exit_claims(env: &Env, group_id: u64) {
+    let key = DataKey::ExitClaims(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+// --- Collateral ---
+
+pub fn get_collateral(env: &Env, group_id: u64, member: &Address) -> i128 {
+    let key = DataKey::Collateral(group_id, member.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_collateral(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let key = DataKey::Collateral(group_id, member.clone());
+    env.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_collateral(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::Collateral(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// --- Position Bids ---
+
+pub fn get_position_bids(env: &Env, group_id: u64) -> Map<Address, i128> {
+    let key = DataKey::PositionBids(group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Map::new(env))
+}
+
+pub fn set_position_bids(env: &Env, group_id: u64, bids: &Map<Address, i128>) {
+    let key = DataKey::PositionBids(group_id);
+    env.storage().persistent().set(&key, bids);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_position_bids(env: &Env, group_id: u64) {
+    let key = DataKey::PositionBids(group_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn get_bid_pool(env: &Env, group_id: u64) -> i128 {
+    let key = DataKey::BidPool(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_bid_pool(env: &Env, group_id: u64, amount: i128) {
+    let key = DataKey::BidPool(group_id);
+    env.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_bid_pool(env: &Env, group_id: u64) {
+    let key = DataKey::BidPool(group_id);
+    env.storage().persistent().remove(&key);
+}
+
 // --- Round ---
 
 pub fn get_round(env: &Env, group_id: u64, round: u32) -> Option<RoundInfo> {
@@ -72,6 +469,22 @@ pub fn set_round(env: &Env, group_id: u64, round_info: &RoundInfo) {
     extend_persistent_ttl(env, &key);
 }
 
+// --- Auction Bids ---
+
+pub fn get_auction_bids(env: &Env, group_id: u64, round: u32) -> Map<Address, i128> {
+    let key = DataKey::AuctionBids(group_id, round);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Map::new(env))
+}
+
+pub fn set_auction_bids(env: &Env, group_id: u64, round: u32, bids: &Map<Address, i128>) {
+    let key = DataKey::AuctionBids(group_id, round);
+    env.storage().persistent().set(&key, bids);
+    extend_persistent_ttl(env, &key);
+}
+
 // --- Member Groups ---
 
 pub fn get_member_groups(env: &Env, member: &Address) -> Vec<u64> {
@@ -103,25 +516,65 @@ pub fn remove_member_group(env: &Env, member: &Address, group_id: u64) {
     extend_persistent_ttl(env, &key);
 }
 
-// --- Dispute ---
+// --- Reputation ---
 
-#[allow(dead_code)]
-pub fn get_dispute(env: &Env, group_id: u64) -> Option<Dispute> {
-    let key = DataKey::Dispute(group_id);
+pub fn get_reputation(env: &Env, member: &Address) -> Option<Reputation> {
+    let key = DataKey::Reputation(member.clone());
     env.storage().persistent().get(&key)
 }
 
-pub fn set_dispute(env: &Env, group_id: u64, dispute: &Dispute) {
-    let key = DataKey::Dispute(group_id);
-    env.storage().persistent().set(&key, dispute);
+pub fn set_reputation(env: &Env, member: &Address, reputation: &Reputation) {
+    let key = DataKey::Reputation(member.clone());
+    env.storage().persistent().set(&key, reputation);
+    extend_persistent_ttl(env, &key);
+}
+
+// --- Defaults ---
+
+pub fn get_default_count(env: &Env, group_id: u64, member: &Address) -> u32 {
+    let key = DataKey::DefaultCount(group_id, member.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_default_count(env: &Env, group_id: u64, member: &Address, count: u32) {
+    let key = DataKey::DefaultCount(group_id, member.clone());
+    env.storage().persistent().set(&key, &count);
     extend_persistent_ttl(env, &key);
 }
 
-pub fn remove_dispute(env: &Env, group_id: u64) {
-    let key = DataKey::Dispute(group_id);
+pub fn remove_default_count(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::DefaultCount(group_id, member.clone());
     env.storage().persistent().remove(&key);
 }
 
+// --- Dispute ---
+
+pub fn get_dispute_counter(env: &Env, group_id: u64) -> u32 {
+    let key = DataKey::DisputeCounter(group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn set_dispute_counter(env: &Env, group_id: u64, counter: u32) {
+    let key = DataKey::DisputeCounter(group_id);
+    env.storage().persistent().set(&key, &counter);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_dispute(env: &Env, group_id: u64, dispute_id: u32) -> Option<Dispute> {
+    let key = DataKey::Dispute(group_id, dispute_id);
+    let result = env.storage().persistent().get(&key);
+    if result.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    result
+}
+
+pub fn set_dispute(env: &Env, group_id: u64, dispute: &Dispute) {
+    let key = DataKey::Dispute(group_id, dispute.id);
+    env.storage().persistent().set(&key, dispute);
+    extend_persistent_ttl(env, &key);
+}
+
 // --- TTL Management ---
 
 fn extend_instance_ttl(env: &Env) {
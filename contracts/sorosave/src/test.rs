@@ -1,7 +1,47 @@
-use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Bytes, BytesN, Env, Map, String,
+};
 
-use crate::types::GroupStatus;
-use crate::{SoroSaveContract, SoroSaveContractClient};
+use crate::asset::RATE_SCALE;
+use crate::errors::ContractError;
+use crate::oracle::MAX_PRICE_AGE;
+use crate::types::{
+    DisputeOutcome, DisputeStatus, GroupStatus, GroupVisibility, PayoutOrderStrategy,
+};
+use crate::upgrade::{LegacyDataKey, RoundInfoV0, SavingsGroupV0};
+use crate::{storage, SoroSaveContract, SoroSaveContractClient};
+use mock_oracle::{MockOracle, MockOracleClient};
+
+mod mock_oracle {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    use crate::oracle::{Asset, PriceData};
+
+    /// SEP-40 price feed whose prices are set by the test.
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, asset: Address, price: i128) {
+            let data = PriceData {
+                price,
+                timestamp: env.ledger().timestamp(),
+            };
+            env.storage().instance().set(&Asset::Stellar(asset), &data);
+        }
+
+        pub fn decimals(_env: Env) -> u32 {
+            14
+        }
+
+        pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+            env.storage().instance().get(&asset)
+        }
+    }
+}
 
 fn setup_env() -> (Env, Address, SoroSaveContractClient<'static>, Address) {
     let env = Env::default();
@@ -35,9 +75,20 @@ fn create_test_group(
         &1_000_000, // 1 token (7 decimals)
         &86400,     // 1 day cycle
         &5,         // max 5 members
+        &0,         // no collateral
+        &PayoutOrderStrategy::JoinOrder,
+        &0, // no minimum reputation
     )
 }
 
+fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(to, &amount);
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
 #[test]
 fn test_create_group() {
     let (env, admin, client, token) = setup_env();
@@ -123,6 +174,9 @@ fn test_full_cycle() {
         &1_000_000,
         &86400,
         &5,
+        &0,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
     );
     client.join_group(&member1, &group_id);
     client.start_group(&admin, &group_id);
@@ -163,6 +217,9 @@ fn test_member_groups() {
         &500_000,
         &43200,
         &3,
+        &0,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
     );
 
     let groups = client.get_member_groups(&admin);
@@ -203,10 +260,17 @@ fn test_dispute_flow() {
         &member1,
         &group_id,
         &String::from_str(&env, "Suspicious activity"),
+        &DisputeOutcome::Dismiss,
     );
     assert_eq!(client.get_group(&group_id).status, GroupStatus::Disputed);
 
-    // Admin resolves
+    // A disputed group cannot be paused and resumed to skip the vote
+    assert_eq!(
+        client.try_pause_group(&admin, &group_id),
+        Err(Ok(ContractError::GroupNotActive))
+    );
+
+    // Protocol admin resolves
     client.resolve_dispute(&admin, &group_id);
     assert_eq!(client.get_group(&group_id).status, GroupStatus::Active);
 }
@@ -219,6 +283,1740 @@ fn test_set_group_admin() {
     let new_admin = Address::generate(&env);
     client.set_group_admin(&admin, &group_id, &new_admin);
 
+    // Nothing changes until the new admin accepts
+    assert_eq!(client.get_group(&group_id).admin, admin);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_accept_group_admin(&stranger, &group_id),
+        Err(Ok(ContractError::NoPendingAdmin))
+    );
+
+    client.accept_group_admin(&new_admin, &group_id);
+
     let group = client.get_group(&group_id);
     assert_eq!(group.admin, new_admin);
+
+    // A proposal cannot be accepted once the group is finished
+    client.set_group_admin(&new_admin, &group_id, &stranger);
+    client.cancel_group(&new_admin, &group_id);
+    assert_eq!(
+        client.try_accept_group_admin(&stranger, &group_id),
+        Err(Ok(ContractError::GroupCancelled))
+    );
+}
+
+#[test]
+fn test_protocol_admin_handover() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_propose_protocol_admin(&new_admin, &new_admin),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.propose_protocol_admin(&admin, &new_admin);
+
+    // The old admin keeps control until the handover is accepted
+    assert_eq!(
+        client.try_accept_protocol_admin(&admin),
+        Err(Ok(ContractError::NoPendingAdmin))
+    );
+    client.accept_protocol_admin(&new_admin);
+
+    assert_eq!(
+        client.try_emergency_withdraw(&admin, &group_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.emergency_withdraw(&new_admin, &group_id);
+}
+
+#[test]
+fn test_close_round_records_defaults() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    mint(&env, &token, &member1, 10_000_000);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+
+    // Cannot close before the deadline
+    assert_eq!(
+        client.try_close_round(&group_id),
+        Err(Ok(ContractError::DeadlineNotReached))
+    );
+
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+
+    let round = client.get_round_status(&group_id, &1);
+    assert!(round.is_complete);
+    assert_eq!(round.defaulters.len(), 1);
+    assert_eq!(round.defaulters.get(0).unwrap(), member2);
+    assert_eq!(client.get_default_count(&member2, &group_id), 1);
+    assert_eq!(client.get_default_count(&member1, &group_id), 0);
+
+    // The round pays out what was collected
+    let token_client = TokenClient::new(&env, &token);
+    let before = token_client.balance(&admin);
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&admin), before + 2_000_000);
+    assert_eq!(client.get_group(&group_id).current_round, 2);
+}
+
+#[test]
+fn test_partial_contributions() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.start_group(&admin, &group_id);
+
+    // Installments accumulate towards the contribution
+    client.contribute_partial(&member1, &group_id, &400_000);
+    assert_eq!(
+        client.get_member_round_balance(&member1, &group_id),
+        400_000
+    );
+    assert!(!client.has_contributed(&member1, &group_id, &1));
+    assert_eq!(
+        client.try_contribute_partial(&member1, &group_id, &700_000),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+
+    // A full contribute pays only what is still owed
+    client.contribute(&member1, &group_id);
+    assert_eq!(
+        client.get_member_round_balance(&member1, &group_id),
+        1_000_000
+    );
+    assert!(client.has_contributed(&member1, &group_id, &1));
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&member1),
+        10_000_000 - 1_000_000
+    );
+    assert_eq!(
+        client.try_contribute_partial(&member1, &group_id, &1),
+        Err(Ok(ContractError::AlreadyContributed))
+    );
+
+    // A member who only paid part of the round is still a defaulter
+    client.contribute_partial(&admin, &group_id, &250_000);
+    assert!(!client.get_round_status(&group_id, &1).is_complete);
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+
+    let round = client.get_round_status(&group_id, &1);
+    assert_eq!(round.defaulters, vec![&env, admin.clone()]);
+    assert_eq!(round.total_contributed, 1_250_000);
+}
+
+#[test]
+fn test_prepay_future_rounds() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    // Only rounds that have not opened yet can be prepaid
+    client.contribute(&member1, &group_id);
+    assert_eq!(
+        client.try_prepay(&member1, &group_id, &3),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.prepay(&member1, &group_id, &2);
+    assert_eq!(token_client.balance(&member1), 10_000_000 - 3_000_000);
+
+    // The next round opens with the prepaid contribution already in
+    client.contribute(&admin, &group_id);
+    client.contribute(&member2, &group_id);
+    client.distribute_payout(&group_id);
+    assert!(client.has_contributed(&member1, &group_id, &2));
+    assert_eq!(
+        client.get_prepayment(&member1, &group_id).unwrap().rounds,
+        1
+    );
+
+    // Ending the group early returns the unused prepayment too
+    client.emergency_withdraw(&admin, &group_id);
+    assert_eq!(token_client.balance(&member1), 10_000_000 - 1_000_000);
+    assert_eq!(client.get_prepayment(&member1, &group_id), None);
+}
+
+#[test]
+fn test_auto_payout_on_last_contribution() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+
+    assert_eq!(
+        client.try_set_auto_payout(&member1, &group_id, &true),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_auto_payout(&admin, &group_id, &true);
+    assert!(client.get_group(&group_id).auto_payout);
+    client.start_group(&admin, &group_id);
+
+    // The contribution that completes the round also pays it out
+    let before = token_client.balance(&admin);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    assert_eq!(token_client.balance(&admin), before - 1_000_000 + 2_000_000);
+    assert_eq!(client.get_group(&group_id).current_round, 2);
+
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+}
+
+#[test]
+fn test_auto_payout_without_contribution() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.set_auto_payout(&admin, &group_id, &true);
+    client.start_group(&admin, &group_id);
+
+    // Round 2 is fully prepaid, so it pays out as soon as it opens
+    for m in [&admin, &member1, &member2] {
+        client.prepay(m, &group_id, &1);
+    }
+    for m in [&admin, &member1, &member2] {
+        client.contribute(m, &group_id);
+    }
+    assert_eq!(client.get_group(&group_id).current_round, 3);
+    assert_eq!(
+        token_client.balance(&member1),
+        10_000_000 - 2_000_000 + 3_000_000
+    );
+
+    // Round 3 completes when the only member yet to pay leaves
+    client.contribute(&admin, &group_id);
+    client.contribute(&member2, &group_id);
+    let before = token_client.balance(&member2);
+    client.exit_group(&member1, &group_id);
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+    assert_eq!(token_client.balance(&member2), before + 3_000_000);
+}
+
+#[test]
+fn test_transfer_seat() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+
+    assert_eq!(
+        client.try_transfer_seat(&member1, &newcomer, &group_id),
+        Err(Ok(ContractError::GroupNotActive))
+    );
+    client.set_transfer_approval(&admin, &group_id, &true);
+    client.start_group(&admin, &group_id);
+
+    // member1 pays into round 1, then hands the seat over
+    client.contribute(&member1, &group_id);
+    assert_eq!(
+        client.try_transfer_seat(&member1, &member2, &group_id),
+        Err(Ok(ContractError::AlreadyMember))
+    );
+    client.transfer_seat(&member1, &newcomer, &group_id);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(
+        group.members,
+        vec![&env, admin.clone(), newcomer.clone(), member2.clone()]
+    );
+    assert_eq!(group.payout_order.get(1).unwrap(), newcomer);
+    assert!(client.has_contributed(&newcomer, &group_id, &1));
+    assert_eq!(client.get_member_groups(&member1).len(), 0);
+    assert_eq!(client.get_member_groups(&newcomer), vec![&env, group_id]);
+
+    // The newcomer receives the seat's payout in round 2
+    mint(&env, &token, &member2, 10_000_000);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member2, &group_id);
+    client.distribute_payout(&group_id);
+    mint(&env, &token, &newcomer, 10_000_000);
+    for m in [&admin, &newcomer, &member2] {
+        client.contribute(m, &group_id);
+    }
+    client.distribute_payout(&group_id);
+    assert_eq!(
+        token_client.balance(&newcomer),
+        10_000_000 - 1_000_000 + 3_000_000
+    );
+
+    // Passing the seat on after its payout hands over the debt to the group too
+    let owed = client.get_exit_settlement(&newcomer, &group_id);
+    assert!(owed < 0);
+    let buyer = Address::generate(&env);
+    client.transfer_seat(&newcomer, &buyer, &group_id);
+    assert_eq!(client.get_exit_settlement(&buyer, &group_id), owed);
+    assert_eq!(client.get_round_status(&group_id, &2).recipient, buyer);
+    assert!(client.has_contributed(&buyer, &group_id, &1));
+}
+
+#[test]
+fn test_exit_group_settlement() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+
+    let setup_group = || {
+        let group_id = create_test_group(&env, &client, &admin, &token);
+        client.set_exit_penalty(&admin, &group_id, &1_000);
+        client.join_group(&member1, &group_id);
+        client.join_group(&member2, &group_id);
+        client.start_group(&admin, &group_id);
+        for m in [&admin, &member1, &member2] {
+            client.contribute(m, &group_id);
+        }
+        client.distribute_payout(&group_id);
+        group_id
+    };
+
+    // The admin was paid round 1 and owes the group the difference plus 10%
+    let group1 = setup_group();
+    assert_eq!(client.get_exit_settlement(&admin, &group1), -2_100_000);
+    let before = token_client.balance(&admin);
+    assert_eq!(client.exit_group(&admin, &group1), -2_100_000);
+    assert_eq!(token_client.balance(&admin), before - 2_100_000);
+
+    let group = client.get_group(&group1);
+    assert_eq!(group.admin, member1);
+    assert_eq!(group.total_rounds, 3);
+
+    // What they paid is shared over the two remaining pots
+    let before = token_client.balance(&member1);
+    client.contribute(&member1, &group1);
+    client.contribute(&member2, &group1);
+    client.distribute_payout(&group1);
+    assert_eq!(
+        token_client.balance(&member1),
+        before - 1_000_000 + 3_050_000
+    );
+
+    // member2 has not been paid yet and is owed their contribution less 10%
+    let group2 = setup_group();
+    let before = token_client.balance(&member2);
+    assert_eq!(client.exit_group(&member2, &group2), 900_000);
+    assert_eq!(client.get_group(&group2).total_rounds, 2);
+
+    // The last round repays them before the pot goes out
+    let before_m1 = token_client.balance(&member1);
+    client.contribute(&admin, &group2);
+    client.contribute(&member1, &group2);
+    client.distribute_payout(&group2);
+    assert_eq!(token_client.balance(&member2), before + 900_000);
+    assert_eq!(
+        token_client.balance(&member1),
+        before_m1 - 1_000_000 + 1_100_000
+    );
+    assert_eq!(client.get_group(&group2).status, GroupStatus::Completed);
+
+    // A last pot too small for the claim pays what it can; the rest is settled
+    // from whatever escrow is left at completion
+    let group3 = setup_group();
+    let before = token_client.balance(&member2);
+    client.exit_group(&member2, &group3);
+    client.contribute_partial(&admin, &group3, &400_000);
+    advance_time(&env, 86401);
+    client.close_round(&group3);
+    client.distribute_payout(&group3);
+    assert_eq!(token_client.balance(&member2), before + 400_000);
+    assert_eq!(client.get_group(&group3).status, GroupStatus::Completed);
+    assert_eq!(client.get_group_balance(&group3), 0);
+}
+
+#[test]
+fn test_remove_member() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+    let reason = String::from_str(&env, "inactive");
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let member3 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.join_group(&member3, &group_id);
+
+    // Anyone can be removed while forming, but only by the admin
+    assert_eq!(
+        client.try_remove_member(&member1, &group_id, &member2, &reason),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.remove_member(&admin, &group_id, &member3, &reason);
+    assert_eq!(client.get_group(&group_id).members.len(), 3);
+    assert_eq!(client.get_member_groups(&member3).len(), 0);
+
+    // Once active, only a member with a recorded default
+    client.start_group(&admin, &group_id);
+    assert_eq!(
+        client.try_remove_member(&admin, &group_id, &member2, &reason),
+        Err(Ok(ContractError::NoDefaultRecorded))
+    );
+
+    mint(&env, &token, &member1, 10_000_000);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+    client.distribute_payout(&group_id);
+
+    client.remove_member(&admin, &group_id, &member2, &reason);
+    let group = client.get_group(&group_id);
+    assert_eq!(group.members, vec![&env, admin.clone(), member1.clone()]);
+    assert_eq!(group.total_rounds, 2);
+}
+
+#[test]
+fn test_remove_paid_out_member_forfeits_collateral() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Collateral Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &4,
+        &2_000_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    let reason = String::from_str(&env, "stopped paying");
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let member3 = Address::generate(&env);
+    for m in [&member1, &member2, &member3] {
+        mint(&env, &token, m, 10_000_000);
+        client.join_group(m, &group_id);
+    }
+    client.start_group(&admin, &group_id);
+
+    // member1 collects round 2, then stops paying
+    for _ in 0..2 {
+        for m in [&admin, &member1, &member2, &member3] {
+            client.contribute(m, &group_id);
+        }
+        client.distribute_payout(&group_id);
+    }
+    for m in [&admin, &member2, &member3] {
+        client.contribute(m, &group_id);
+    }
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+    client.distribute_payout(&group_id);
+    assert_eq!(client.get_collateral(&member1, &group_id), 1_000_000);
+
+    // What is left of their collateral stays with the group
+    let before = token_client.balance(&member1);
+    client.remove_member(&admin, &group_id, &member1, &reason);
+    assert_eq!(token_client.balance(&member1), before);
+    assert_eq!(client.get_collateral(&member1, &group_id), 0);
+
+    // and tops up the last pot. member3 also gets their own collateral back.
+    let before = token_client.balance(&member3);
+    for m in [&admin, &member2, &member3] {
+        client.contribute(m, &group_id);
+    }
+    client.distribute_payout(&group_id);
+    assert_eq!(
+        token_client.balance(&member3),
+        before - 1_000_000 + 4_000_000 + 2_000_000
+    );
+}
+
+#[test]
+fn test_dispute_removal_forfeits_paid_out_collateral() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Collateral Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &3,
+        &2_000_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    for m in [&member1, &member2] {
+        mint(&env, &token, m, 10_000_000);
+        client.join_group(m, &group_id);
+    }
+    client.start_group(&admin, &group_id);
+
+    // member1 collects round 2, then the group votes them out
+    for _ in 0..2 {
+        for m in [&admin, &member1, &member2] {
+            client.contribute(m, &group_id);
+        }
+        client.distribute_payout(&group_id);
+    }
+    let proposal = DisputeOutcome::RemoveMember(member1.clone());
+    client.raise_dispute(
+        &member2,
+        &group_id,
+        &String::from_str(&env, "Took the pot and left"),
+        &proposal,
+    );
+    let before = token_client.balance(&member1);
+    client.vote_dispute(&member2, &group_id, &proposal);
+    client.vote_dispute(&admin, &group_id, &proposal);
+
+    // Their collateral stays with the group and tops up the last pot
+    assert_eq!(client.get_group(&group_id).members.len(), 2);
+    assert_eq!(token_client.balance(&member1), before);
+    assert_eq!(client.get_collateral(&member1, &group_id), 0);
+
+    let before = token_client.balance(&member2);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member2, &group_id);
+    client.distribute_payout(&group_id);
+    assert_eq!(
+        token_client.balance(&member2),
+        before - 1_000_000 + 4_000_000 + 2_000_000
+    );
+}
+
+#[test]
+fn test_reputation_gates_joining() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+    assert_eq!(client.get_reputation(&member1).score, 0);
+
+    // member1 pays round 1 on time and round 2 late; member2 defaults on both
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+    client.distribute_payout(&group_id);
+
+    advance_time(&env, 86401);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.close_round(&group_id);
+    client.distribute_payout(&group_id);
+
+    advance_time(&env, 86401);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.close_round(&group_id);
+    client.distribute_payout(&group_id);
+
+    let rep = client.get_reputation(&member1);
+    assert_eq!((rep.rounds_on_time, rep.late_payments), (1, 2));
+    assert_eq!(rep.groups_completed, 1);
+    assert_eq!(rep.score, 10 + 50 - 50);
+    assert_eq!(client.get_reputation(&member2).defaults, 3);
+    assert_eq!(client.get_reputation(&member2).score, 0);
+
+    // A new group only admits members above its minimum score
+    let group2 = client.create_group(
+        &admin,
+        &String::from_str(&env, "Trusted Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &5,
+        &0,
+        &PayoutOrderStrategy::JoinOrder,
+        &10,
+    );
+    client.join_group(&member1, &group2);
+    assert_eq!(
+        client.try_join_group(&member2, &group2),
+        Err(Ok(ContractError::ReputationTooLow))
+    );
+
+    // A fresh address has no track record to rank on
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_join_group(&stranger, &group2),
+        Err(Ok(ContractError::ReputationTooLow))
+    );
+
+    // nor can a seat be handed to someone the group would not admit
+    client.start_group(&admin, &group2);
+    assert_eq!(
+        client.try_transfer_seat(&member1, &stranger, &group2),
+        Err(Ok(ContractError::ReputationTooLow))
+    );
+}
+
+#[test]
+fn test_allowlist_and_invite_code() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let friend = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    // Allowlist groups only take approved addresses
+    client.set_visibility(&admin, &group_id, &GroupVisibility::Allowlist);
+    assert_eq!(
+        client.try_join_group(&stranger, &group_id),
+        Err(Ok(ContractError::NotInvited))
+    );
+    client.approve_join(&admin, &group_id, &friend);
+    assert_eq!(client.get_allowlist(&group_id), vec![&env, friend.clone()]);
+    client.join_group(&friend, &group_id);
+
+    // Invite-code groups check the code against the published hash
+    let code = Bytes::from_slice(&env, b"sorosave-circle");
+    let hash: BytesN<32> = env.crypto().sha256(&code).into();
+    client.set_visibility(&admin, &group_id, &GroupVisibility::InviteCode);
+    client.set_invite_code(&admin, &group_id, &hash);
+    assert_eq!(
+        client.try_join_group_with_code(&stranger, &group_id, &Bytes::from_slice(&env, b"guess")),
+        Err(Ok(ContractError::InvalidInviteCode))
+    );
+    client.join_group_with_code(&stranger, &group_id, &code);
+    assert_eq!(client.get_group(&group_id).members.len(), 3);
+
+    // The code stops working once the group is no longer invite-only
+    let latecomer = Address::generate(&env);
+    client.set_visibility(&admin, &group_id, &GroupVisibility::Allowlist);
+    assert_eq!(
+        client.try_join_group_with_code(&latecomer, &group_id, &code),
+        Err(Ok(ContractError::NotInvited))
+    );
+}
+
+#[test]
+fn test_join_requests() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Semi-private Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &5,
+        &500_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    client.set_visibility(&admin, &group_id, &GroupVisibility::Allowlist);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+
+    // Requests hold the collateral until the admin decides
+    client.request_join(&member1, &group_id);
+    client.request_join(&member2, &group_id);
+    assert_eq!(
+        client.try_request_join(&member1, &group_id),
+        Err(Ok(ContractError::AlreadyRequested))
+    );
+    assert_eq!(client.get_join_requests(&group_id).len(), 2);
+    assert_eq!(token_client.balance(&member1), 9_500_000);
+
+    client.approve_join(&admin, &group_id, &member1);
+    client.reject_join(&admin, &group_id, &member2);
+    assert_eq!(client.get_join_requests(&group_id).len(), 0);
+    assert_eq!(
+        client.try_reject_join(&admin, &group_id, &member2),
+        Err(Ok(ContractError::NoJoinRequest))
+    );
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.members, vec![&env, admin.clone(), member1.clone()]);
+    assert_eq!(client.get_collateral(&member1, &group_id), 500_000);
+    assert_eq!(token_client.balance(&member1), 9_500_000);
+    assert_eq!(token_client.balance(&member2), 10_000_000);
+
+    // Requests still pending when the group starts are turned down
+    client.request_join(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+    assert_eq!(client.get_join_requests(&group_id).len(), 0);
+    assert_eq!(client.get_collateral(&member2, &group_id), 0);
+    assert_eq!(token_client.balance(&member2), 10_000_000);
+
+    // A requester can withdraw without waiting on the admin
+    let group2 = client.create_group(
+        &admin,
+        &String::from_str(&env, "Another Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &5,
+        &500_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    client.request_join(&member2, &group2);
+    client.cancel_join_request(&member2, &group2);
+    assert_eq!(token_client.balance(&member2), 10_000_000);
+    assert_eq!(
+        client.try_cancel_join_request(&member2, &group2),
+        Err(Ok(ContractError::NoJoinRequest))
+    );
+
+    // and winding a forming group up returns pending requests' collateral too
+    client.request_join(&member2, &group2);
+    client.emergency_withdraw(&admin, &group2);
+    assert_eq!(client.get_join_requests(&group2).len(), 0);
+    assert_eq!(token_client.balance(&member2), 10_000_000);
+}
+
+#[test]
+fn test_auto_start() {
+    let (env, admin, client, token) = setup_env();
+
+    // Fills up and starts on the last seat
+    let group1 = create_strategy_group(
+        &env,
+        &client,
+        &admin,
+        &token,
+        PayoutOrderStrategy::JoinOrder,
+    );
+    client.set_auto_start(&admin, &group1, &0, &true);
+    client.join_group(&Address::generate(&env), &group1);
+    assert_eq!(
+        client.try_try_start(&group1),
+        Err(Ok(ContractError::DeadlineNotReached))
+    );
+    client.join_group(&Address::generate(&env), &group1);
+    assert_eq!(client.get_group(&group1).status, GroupStatus::Active);
+
+    // Starts on schedule once anyone pokes it
+    let group2 = create_test_group(&env, &client, &admin, &token);
+    let start_at = env.ledger().timestamp() + 3600;
+    client.set_auto_start(&admin, &group2, &start_at, &false);
+    client.join_group(&Address::generate(&env), &group2);
+    assert_eq!(
+        client.try_try_start(&group2),
+        Err(Ok(ContractError::DeadlineNotReached))
+    );
+    advance_time(&env, 3600);
+    client.try_start(&group2);
+
+    let group = client.get_group(&group2);
+    assert_eq!(group.status, GroupStatus::Active);
+    assert_eq!(group.total_rounds, 2);
+}
+
+#[test]
+fn test_cancel_forming_group() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Abandoned Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &5,
+        &500_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+
+    let member1 = Address::generate(&env);
+    let applicant = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &applicant, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.request_join(&applicant, &group_id);
+
+    assert_eq!(
+        client.try_cancel_group(&member1, &group_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.cancel_group(&admin, &group_id);
+
+    // Every deposit is returned and the group drops out of member indexes
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Cancelled);
+    assert_eq!(token_client.balance(&admin), 10_000_000);
+    assert_eq!(token_client.balance(&member1), 10_000_000);
+    assert_eq!(token_client.balance(&applicant), 10_000_000);
+    assert_eq!(client.get_member_groups(&member1).len(), 0);
+    assert_eq!(client.get_member_groups(&admin).len(), 0);
+
+    assert_eq!(
+        client.try_cancel_group(&admin, &group_id),
+        Err(Ok(ContractError::GroupNotForming))
+    );
+    assert_eq!(
+        client.try_emergency_withdraw(&admin, &group_id),
+        Err(Ok(ContractError::GroupCancelled))
+    );
+}
+
+#[test]
+fn test_emergency_withdraw_uses_group_escrow() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+
+    // Two groups share the same token
+    let group1 = create_test_group(&env, &client, &admin, &token);
+    let group2 = create_test_group(&env, &client, &admin, &token);
+    for group_id in [group1, group2] {
+        client.join_group(&member1, &group_id);
+        client.start_group(&admin, &group_id);
+        client.contribute(&admin, &group_id);
+    }
+    client.contribute(&member1, &group2);
+
+    assert_eq!(client.get_group_balance(&group1), 1_000_000);
+    assert_eq!(client.get_group_balance(&group2), 2_000_000);
+
+    let before = token_client.balance(&admin);
+    client.emergency_withdraw(&admin, &group1);
+
+    // Only group 1's escrow is refunded; group 2's funds stay put
+    assert_eq!(token_client.balance(&admin), before + 1_000_000);
+    assert_eq!(client.get_group_balance(&group1), 0);
+    assert_eq!(client.get_group_balance(&group2), 2_000_000);
+    assert_eq!(token_client.balance(&client.address), 2_000_000);
+
+    client.distribute_payout(&group2);
+    assert_eq!(client.get_group_balance(&group2), 0);
+}
+
+#[test]
+fn test_emergency_withdraw_refunds_actual_contributions() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+
+    // Round 1 completes and pays out, round 2 is left half-funded
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.contribute(&member2, &group_id);
+    client.distribute_payout(&group_id);
+    client.contribute(&member1, &group_id);
+
+    let admin_before = token_client.balance(&admin);
+    let member1_before = token_client.balance(&member1);
+    let member2_before = token_client.balance(&member2);
+
+    client.emergency_withdraw(&admin, &group_id);
+
+    assert_eq!(token_client.balance(&admin), admin_before);
+    assert_eq!(token_client.balance(&member1), member1_before + 1_000_000);
+    assert_eq!(token_client.balance(&member2), member2_before);
+    assert_eq!(client.get_group_balance(&group_id), 0);
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+}
+
+#[test]
+fn test_collateral_locked_and_released() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Collateral Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &2,
+        &500_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    assert_eq!(client.get_collateral(&admin, &group_id), 500_000);
+
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    assert_eq!(client.get_collateral(&member1, &group_id), 500_000);
+    assert_eq!(token_client.balance(&member1), 9_500_000);
+
+    client.start_group(&admin, &group_id);
+    for _ in 0..2 {
+        client.contribute(&admin, &group_id);
+        client.contribute(&member1, &group_id);
+        client.distribute_payout(&group_id);
+    }
+
+    // Collateral comes back once the final round has paid out
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+    assert_eq!(client.get_collateral(&member1, &group_id), 0);
+    assert_eq!(token_client.balance(&member1), 10_000_000);
+}
+
+#[test]
+fn test_collateral_slashed_on_default() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Collateral Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &2,
+        &1_500_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.start_group(&admin, &group_id);
+
+    // Admin receives round 1; member1 never pays
+    client.contribute(&admin, &group_id);
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+
+    let round = client.get_round_status(&group_id, &1);
+    assert_eq!(round.total_contributed, 2_000_000);
+    assert_eq!(client.get_collateral(&member1, &group_id), 500_000);
+
+    let before = token_client.balance(&admin);
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&admin), before + 2_000_000);
+}
+
+#[test]
+fn test_leave_group_releases_collateral() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Collateral Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &5,
+        &500_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 1_000_000);
+    client.join_group(&member1, &group_id);
+    client.leave_group(&member1, &group_id);
+
+    assert_eq!(client.get_collateral(&member1, &group_id), 0);
+    assert_eq!(token_client.balance(&member1), 1_000_000);
+}
+
+fn create_strategy_group(
+    env: &Env,
+    client: &SoroSaveContractClient,
+    admin: &Address,
+    token: &Address,
+    strategy: PayoutOrderStrategy,
+) -> u64 {
+    client.create_group(
+        admin,
+        &String::from_str(env, "Strategy Group"),
+        token,
+        &1_000_000,
+        &86400,
+        &3,
+        &0,
+        &strategy,
+        &0,
+    )
+}
+
+#[test]
+fn test_explicit_payout_order() {
+    let (env, admin, client, token) = setup_env();
+    let group_id =
+        create_strategy_group(&env, &client, &admin, &token, PayoutOrderStrategy::Explicit);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+
+    // An order that does not cover every member is rejected at start
+    client.set_payout_order(
+        &admin,
+        &group_id,
+        &vec![&env, member2.clone(), admin.clone()],
+    );
+    assert_eq!(
+        client.try_start_group(&admin, &group_id),
+        Err(Ok(ContractError::InvalidPayoutOrder))
+    );
+
+    let order = vec![&env, member2.clone(), admin.clone(), member1.clone()];
+    client.set_payout_order(&admin, &group_id, &order);
+    client.start_group(&admin, &group_id);
+
+    assert_eq!(client.get_payout_order(&group_id), order);
+    assert_eq!(client.get_current_recipient(&group_id), member2);
+}
+
+#[test]
+fn test_random_payout_order() {
+    let (env, admin, client, token) = setup_env();
+    let group_id =
+        create_strategy_group(&env, &client, &admin, &token, PayoutOrderStrategy::Random);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    let order = client.get_payout_order(&group_id);
+    assert_eq!(order.len(), 3);
+    assert!(order.contains(&admin));
+    assert!(order.contains(&member1));
+    assert!(order.contains(&member2));
+}
+
+#[test]
+fn test_bid_payout_order() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_strategy_group(&env, &client, &admin, &token, PayoutOrderStrategy::Bid);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+
+    client.bid_for_position(&member2, &group_id, &200_000);
+    client.bid_for_position(&member1, &group_id, &100_000);
+    assert_eq!(
+        client.try_bid_for_position(&member1, &group_id, &50_000),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.start_group(&admin, &group_id);
+
+    let order = client.get_payout_order(&group_id);
+    assert_eq!(
+        order,
+        vec![&env, member2.clone(), member1.clone(), admin.clone()]
+    );
+
+    for _ in 0..2 {
+        client.contribute(&admin, &group_id);
+        client.contribute(&member1, &group_id);
+        client.contribute(&member2, &group_id);
+        client.distribute_payout(&group_id);
+    }
+
+    // The last recipient collects the pot plus all premiums
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.contribute(&member2, &group_id);
+    let before = token_client.balance(&admin);
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&admin), before + 3_300_000);
+    assert_eq!(client.get_group_balance(&group_id), 0);
+}
+
+#[test]
+fn test_auction_round() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id =
+        create_strategy_group(&env, &client, &admin, &token, PayoutOrderStrategy::Auction);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &member2, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.contribute(&member2, &group_id);
+
+    // Payout waits for the auction
+    assert_eq!(
+        client.try_distribute_payout(&group_id),
+        Err(Ok(ContractError::AuctionNotSettled))
+    );
+
+    client.place_bid(&member1, &group_id, &2_800_000);
+    client.place_bid(&member2, &group_id, &2_600_000);
+    assert_eq!(
+        client.try_settle_auction(&group_id),
+        Err(Ok(ContractError::DeadlineNotReached))
+    );
+    client.place_bid(&admin, &group_id, &2_900_000);
+
+    // Everyone unpaid has bid, so the auction can settle early
+    client.settle_auction(&group_id);
+    assert_eq!(client.get_current_recipient(&group_id), member2);
+    assert_eq!(client.get_round_status(&group_id, &1).discount, 400_000);
+
+    let admin_before = token_client.balance(&admin);
+    let member1_before = token_client.balance(&member1);
+    let member2_before = token_client.balance(&member2);
+    client.distribute_payout(&group_id);
+
+    assert_eq!(token_client.balance(&member2), member2_before + 2_600_000);
+    assert_eq!(token_client.balance(&admin), admin_before + 200_000);
+    assert_eq!(token_client.balance(&member1), member1_before + 200_000);
+    assert_eq!(client.get_group_balance(&group_id), 0);
+
+    // A paid member can no longer bid
+    assert_eq!(
+        client.try_place_bid(&member2, &group_id, &1_000_000),
+        Err(Ok(ContractError::AlreadyPaidOut))
+    );
+
+    // With no bids the first unpaid member takes the full pot after the deadline
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.contribute(&member2, &group_id);
+    advance_time(&env, 86401);
+    client.settle_auction(&group_id);
+    assert_eq!(client.get_current_recipient(&group_id), admin);
+    assert_eq!(client.get_round_status(&group_id, &2).discount, 0);
+    client.distribute_payout(&group_id);
+
+    // Final round has a single eligible member and settles immediately
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.contribute(&member2, &group_id);
+    client.settle_auction(&group_id);
+    client.distribute_payout(&group_id);
+
+    assert_eq!(
+        client.get_payout_order(&group_id),
+        vec![&env, member2.clone(), admin.clone(), member1.clone()]
+    );
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+}
+
+#[test]
+fn test_auction_bid_above_shrunken_pot() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id =
+        create_strategy_group(&env, &client, &admin, &token, PayoutOrderStrategy::Auction);
+
+    // Another group's escrow shares the contract's balance
+    let other = create_test_group(&env, &client, &admin, &token);
+    let outsider = Address::generate(&env);
+    client.join_group(&outsider, &other);
+    client.start_group(&admin, &other);
+    client.contribute(&admin, &other);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    // member1 bids the full pot, then member2 leaves before the auction settles
+    client.place_bid(&member1, &group_id, &3_000_000);
+    client.exit_group(&member2, &group_id);
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    advance_time(&env, 86401);
+    client.settle_auction(&group_id);
+    assert_eq!(client.get_round_status(&group_id, &1).discount, 0);
+
+    // The winner takes no more than was collected
+    let before = token_client.balance(&member1);
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&member1), before + 2_000_000);
+    assert_eq!(client.get_group_balance(&other), 1_000_000);
+    assert_eq!(token_client.balance(&client.address), 1_000_000);
+}
+#[test]
+fn test_dispute_vote_removes_member() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    mint(&env, &token, &member2, 10_000_000);
+    client.contribute(&member2, &group_id);
+
+    let proposal = DisputeOutcome::RemoveMember(member2.clone());
+    client.raise_dispute(
+        &member1,
+        &group_id,
+        &String::from_str(&env, "Threatening other members"),
+        &proposal,
+    );
+
+    client.vote_dispute(&member1, &group_id, &proposal);
+    assert_eq!(
+        client.try_vote_dispute(&member1, &group_id, &proposal),
+        Err(Ok(ContractError::AlreadyVoted))
+    );
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Disputed);
+
+    // Second vote is a majority of three members and executes the removal
+    client.vote_dispute(&admin, &group_id, &proposal);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.status, GroupStatus::Active);
+    assert_eq!(group.members, vec![&env, admin.clone(), member1.clone()]);
+    assert_eq!(
+        group.payout_order,
+        vec![&env, admin.clone(), member1.clone()]
+    );
+    assert_eq!(group.total_rounds, 2);
+    assert_eq!(client.get_member_groups(&member2).len(), 0);
+
+    // Their round contribution is returned
+    assert_eq!(token_client.balance(&member2), 10_000_000);
+    assert_eq!(client.get_group_balance(&group_id), 0);
+}
+
+#[test]
+fn test_dispute_without_quorum_is_dismissed() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.join_group(&member2, &group_id);
+    client.start_group(&admin, &group_id);
+
+    let proposal = DisputeOutcome::ReplaceAdmin(member1.clone());
+    client.raise_dispute(
+        &member1,
+        &group_id,
+        &String::from_str(&env, "Admin is unresponsive"),
+        &proposal,
+    );
+    client.vote_dispute(&member1, &group_id, &proposal);
+
+    // The group admin cannot shut down a dispute about themselves
+    let protocol_admin = admin.clone();
+    let group_admin = Address::generate(&env);
+    client.set_group_admin(&protocol_admin, &group_id, &group_admin);
+    client.accept_group_admin(&group_admin, &group_id);
+    assert_eq!(
+        client.try_resolve_dispute(&group_admin, &group_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    assert_eq!(
+        client.try_finalize_dispute(&group_id),
+        Err(Ok(ContractError::VotingOpen))
+    );
+    advance_time(&env, 3 * 86400 + 1);
+    assert_eq!(
+        client.try_vote_dispute(&admin, &group_id, &proposal),
+        Err(Ok(ContractError::VotingClosed))
+    );
+
+    client.finalize_dispute(&group_id);
+    let group = client.get_group(&group_id);
+    assert_eq!(group.status, GroupStatus::Active);
+    assert_eq!(group.admin, group_admin);
+}
+
+#[test]
+fn test_dispute_vote_dissolves_group() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.start_group(&admin, &group_id);
+
+    let before = token_client.balance(&admin);
+    client.contribute(&admin, &group_id);
+
+    client.raise_dispute(
+        &member1,
+        &group_id,
+        &String::from_str(&env, "Group cannot continue"),
+        &DisputeOutcome::DissolveGroup,
+    );
+    client.vote_dispute(&member1, &group_id, &DisputeOutcome::DissolveGroup);
+    client.vote_dispute(&admin, &group_id, &DisputeOutcome::DissolveGroup);
+
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+    assert_eq!(token_client.balance(&admin), before);
+}
+
+#[test]
+fn test_dispute_history_and_evidence() {
+    let (env, admin, client, token) = setup_env();
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    let member1 = Address::generate(&env);
+    client.join_group(&member1, &group_id);
+    client.start_group(&admin, &group_id);
+
+    client.raise_dispute(
+        &member1,
+        &group_id,
+        &String::from_str(&env, "Late payout"),
+        &DisputeOutcome::Dismiss,
+    );
+    let evidence = String::from_str(&env, "ipfs://bafybeigdyrzt5");
+    client.add_evidence(&member1, &group_id, &1, &evidence);
+    client.resolve_dispute(&admin, &group_id);
+
+    // Evidence is frozen once the dispute is resolved
+    assert_eq!(
+        client.try_add_evidence(&member1, &group_id, &1, &evidence),
+        Err(Ok(ContractError::DisputeResolved))
+    );
+
+    client.raise_dispute(
+        &admin,
+        &group_id,
+        &String::from_str(&env, "Second issue"),
+        &DisputeOutcome::Dismiss,
+    );
+
+    let disputes = client.list_disputes(&group_id);
+    assert_eq!(disputes.len(), 2);
+
+    let first = client.get_dispute(&group_id, &1);
+    assert_eq!(first.raised_by, member1);
+    assert_eq!(first.evidence, vec![&env, evidence]);
+    assert_eq!(
+        first.status,
+        DisputeStatus::Resolved(DisputeOutcome::Dismiss)
+    );
+
+    let second = client.get_dispute(&group_id, &2);
+    assert_eq!(second.raised_by, admin);
+    assert_eq!(second.status, DisputeStatus::Open);
+    assert_eq!(
+        client.try_get_dispute(&group_id, &3).unwrap_err(),
+        Ok(ContractError::DisputeNotFound)
+    );
+}
+
+#[test]
+fn test_protocol_fees() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let treasury = Address::generate(&env);
+
+    // Only the protocol admin can configure fees, and rates are capped
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_fee_config(&stranger, &treasury, &100, &50),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_fee_config(&admin, &treasury, &5_000, &50),
+        Err(Ok(ContractError::InvalidFee))
+    );
+    client.set_fee_config(&admin, &treasury, &100, &50);
+
+    let group_id = create_test_group(&env, &client, &admin, &token);
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.start_group(&admin, &group_id);
+
+    // 0.5% on top of each contribution
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    assert_eq!(token_client.balance(&member1), 10_000_000 - 1_005_000);
+    assert_eq!(token_client.balance(&treasury), 10_000);
+
+    // 1% out of the payout
+    let before = token_client.balance(&admin);
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&admin), before + 1_980_000);
+    assert_eq!(token_client.balance(&treasury), 30_000);
+
+    // A per-token override replaces the default rates
+    client.set_token_fee(&admin, &token, &0, &0);
+    assert_eq!(client.get_fee_rates(&token).payout_bps, 0);
+    client.remove_token_fee(&admin, &token);
+    assert_eq!(client.get_fee_rates(&token).payout_bps, 100);
+}
+
+#[test]
+fn test_keeper_rewards() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let treasury = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    // Keepers get half of each payout fee
+    client.set_fee_config(&admin, &treasury, &100, &0);
+    assert_eq!(
+        client.try_set_keeper_fee_share(&admin, &10_001),
+        Err(Ok(ContractError::InvalidFee))
+    );
+    client.set_keeper_fee_share(&admin, &5_000);
+
+    let group_id = create_test_group(&env, &client, &admin, &token);
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+
+    // The group also pays a flat reward from its own pool
+    client.set_keeper_reward(&admin, &group_id, &4_500);
+    client.fund_keeper_pool(&admin, &group_id, &4_000);
+    client.fund_keeper_pool(&member1, &group_id, &8_000);
+    assert_eq!(client.get_keeper_pool(&group_id), 12_000);
+    client.start_group(&admin, &group_id);
+
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    client.distribute_payout_as_keeper(&group_id, &keeper);
+    assert_eq!(token_client.balance(&keeper), 10_000 + 4_500);
+    assert_eq!(token_client.balance(&treasury), 10_000);
+
+    // Round closes pay the pool reward too
+    advance_time(&env, 86401);
+    let admin_before = token_client.balance(&admin);
+    let member1_before = token_client.balance(&member1);
+    client.close_round_as_keeper(&group_id, &keeper);
+    assert_eq!(token_client.balance(&keeper), 14_500 + 4_500);
+
+    // A plain call pays no keeper
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&keeper), 19_000);
+
+    // The group has ended; the rest of the pool goes back to its funders pro rata
+    assert_eq!(client.get_group(&group_id).status, GroupStatus::Completed);
+    assert_eq!(client.get_keeper_pool(&group_id), 0);
+    assert_eq!(token_client.balance(&admin), admin_before + 1_000);
+    assert_eq!(token_client.balance(&member1), member1_before + 2_000);
+}
+
+#[test]
+fn test_multi_token_group() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let eurc = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let eurc_client = TokenClient::new(&env, &eurc);
+    let group_id = create_test_group(&env, &client, &admin, &token);
+
+    // 1 EURC is worth 1.1 of the payout token
+    assert_eq!(
+        client.try_add_accepted_token(&admin, &group_id, &token, &11_000_000),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.add_accepted_token(&admin, &group_id, &eurc, &11_000_000);
+    assert_eq!(client.get_accepted_tokens(&group_id).len(), 1);
+
+    let member1 = Address::generate(&env);
+    mint(&env, &eurc, &member1, 10_000_000);
+    client.join_group(&member1, &group_id);
+    client.set_contribution_token(&member1, &group_id, &eurc);
+    assert_eq!(client.get_contribution_token(&member1, &group_id), eurc);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_set_contribution_token(&member1, &group_id, &other),
+        Err(Ok(ContractError::AssetNotAccepted))
+    );
+
+    client.start_group(&admin, &group_id);
+    assert_eq!(
+        client.try_add_accepted_token(&admin, &group_id, &other, &RATE_SCALE),
+        Err(Ok(ContractError::GroupNotForming))
+    );
+
+    // Contributions are valued in the payout token, paid in the member's token
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    assert_eq!(eurc_client.balance(&member1), 10_000_000 - 909_091);
+    assert_eq!(
+        client.get_round_status(&group_id, &1).total_contributed,
+        2_000_000
+    );
+    assert_eq!(client.get_asset_balance(&group_id, &eurc), 909_091);
+
+    // The recipient receives each token's share of the pot in kind
+    let before = token_client.balance(&admin);
+    client.distribute_payout(&group_id);
+    assert_eq!(token_client.balance(&admin), before + 1_000_000);
+    assert_eq!(eurc_client.balance(&admin), 909_091);
+    assert_eq!(client.get_asset_balance(&group_id, &eurc), 0);
+}
+
+#[test]
+fn test_oracle_denominated_group() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let eurc = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let eurc_client = TokenClient::new(&env, &eurc);
+
+    // The payout token trades at 0.10 and EURC at 1.10 of the reference currency
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+    oracle.set_price(&token, &10_000_000_000_000);
+    oracle.set_price(&eurc, &110_000_000_000_000);
+
+    let group_id = create_test_group(&env, &client, &admin, &token);
+    client.add_accepted_token(&admin, &group_id, &eurc, &RATE_SCALE);
+    client.set_price_oracle(&admin, &group_id, &Some(oracle_id.clone()));
+    assert_eq!(client.get_price_oracle(&group_id), Some(oracle_id));
+
+    let member1 = Address::generate(&env);
+    mint(&env, &eurc, &member1, 10_000_000);
+    mint(&env, &token, &admin, 30_000_000);
+    client.join_group(&member1, &group_id);
+    client.set_contribution_token(&member1, &group_id, &eurc);
+    client.start_group(&admin, &group_id);
+
+    // Each member pays the reference amount in their own token
+    client.contribute(&admin, &group_id);
+    client.contribute(&member1, &group_id);
+    assert_eq!(token_client.balance(&admin), 40_000_000 - 10_000_000);
+    assert_eq!(eurc_client.balance(&member1), 10_000_000 - 909_091);
+    client.distribute_payout(&group_id);
+
+    // Stale prices are refused
+    advance_time(&env, MAX_PRICE_AGE + 1);
+    assert_eq!(
+        client.try_contribute(&admin, &group_id),
+        Err(Ok(ContractError::PriceUnavailable))
+    );
+
+    // The amount owed follows the price
+    oracle.set_price(&token, &5_000_000_000_000);
+    let before = token_client.balance(&admin);
+    client.contribute(&admin, &group_id);
+    assert_eq!(token_client.balance(&admin), before - 20_000_000);
+}
+
+#[test]
+fn test_close_round_with_stale_price() {
+    let (env, admin, client, token) = setup_env();
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+    oracle.set_price(&token, &10_000_000_000_000);
+
+    let group_id = client.create_group(
+        &admin,
+        &String::from_str(&env, "Priced Group"),
+        &token,
+        &1_000_000,
+        &86400,
+        &5,
+        &5_000_000,
+        &PayoutOrderStrategy::JoinOrder,
+        &0,
+    );
+    client.set_price_oracle(&admin, &group_id, &Some(oracle_id));
+
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+    mint(&env, &token, &admin, 30_000_000);
+    client.join_group(&member1, &group_id);
+    client.start_group(&admin, &group_id);
+    client.contribute(&admin, &group_id);
+
+    // The price goes stale before the deadline passes
+    advance_time(&env, 86401);
+    client.close_round(&group_id);
+
+    // The default is recorded and the round closed; the collateral is untouched
+    assert_eq!(client.get_default_count(&member1, &group_id), 1);
+    assert!(client.get_round_status(&group_id, &1).is_complete);
+    assert_eq!(client.get_collateral(&member1, &group_id), 5_000_000);
+}
+
+#[test]
+fn test_migrate_v0_groups() {
+    let (env, admin, client, token) = setup_env();
+    let token_client = TokenClient::new(&env, &token);
+    let member1 = Address::generate(&env);
+    mint(&env, &token, &member1, 10_000_000);
+
+    // Group 1 has a round still collecting, group 2 a full round not yet paid out
+    let group1 = create_test_group(&env, &client, &admin, &token);
+    let group2 = create_test_group(&env, &client, &admin, &token);
+    for group_id in [group1, group2] {
+        client.join_group(&member1, &group_id);
+        client.start_group(&admin, &group_id);
+        client.contribute(&admin, &group_id);
+    }
+    client.contribute(&member1, &group2);
+    assert!(client.get_round_status(&group2, &1).is_complete);
+
+    assert_eq!(
+        client.try_migrate(&admin, &1, &2),
+        Err(Ok(ContractError::AlreadyMigrated))
+    );
+
+    // Rewrite the groups and their rounds in the original layout, as an old
+    // deployment would hold them. Version 0 kept no escrow record.
+    let groups = [client.get_group(&group1), client.get_group(&group2)];
+    for group in groups.iter() {
+        let round = client.get_round_status(&group.id, &1);
+        env.as_contract(&client.address, || {
+            storage::set_group_balance(&env, group.id, 0);
+            let mut contributions = Map::new(&env);
+            for member in round.contributions.keys() {
+                contributions.set(member, true);
+            }
+            let old_round = RoundInfoV0 {
+                round_number: round.round_number,
+                recipient: round.recipient.clone(),
+                contributions,
+                total_contributed: round.total_contributed,
+                is_complete: round.is_complete,
+                deadline: round.deadline,
+            };
+            env.storage()
+                .persistent()
+                .set(&LegacyDataKey::Round(group.id, 1), &old_round);
+
+            let old = SavingsGroupV0 {
+                id: group.id,
+                name: group.name.clone(),
+                admin: group.admin.clone(),
+                token: group.token.clone(),
+                contribution_amount: group.contribution_amount,
+                cycle_length: group.cycle_length,
+                max_members: group.max_members,
+                members: group.members.clone(),
+                payout_order: group.payout_order.clone(),
+                current_round: group.current_round,
+                total_rounds: group.total_rounds,
+                status: group.status.clone(),
+                created_at: group.created_at,
+            };
+            env.storage()
+                .persistent()
+                .set(&LegacyDataKey::Group(group.id), &old);
+            storage::set_schema_version(&env, 0);
+        });
+    }
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_migrate(&stranger, &1, &1),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Batches run in order, one group at a time here
+    assert_eq!(
+        client.try_migrate(&admin, &2, &2),
+        Err(Ok(ContractError::InvalidMigrationRange))
+    );
+    assert_eq!(client.migrate(&admin, &1, &1), 0);
+    assert_eq!(client.get_schema_version(), 0);
+    assert_eq!(
+        client.try_migrate(&admin, &1, &1),
+        Err(Ok(ContractError::InvalidMigrationRange))
+    );
+    assert_eq!(client.migrate(&admin, &2, &10), 1);
+    assert_eq!(client.get_schema_version(), 1);
+
+    let migrated = client.get_group(&group1);
+    assert_eq!(migrated.members, groups[0].members);
+    assert_eq!(migrated.collateral, 0);
+    assert_eq!(migrated.payout_strategy, PayoutOrderStrategy::JoinOrder);
+    assert_eq!(migrated.status, GroupStatus::Active);
+    assert_eq!(client.get_round_status(&group1, &1).discount, 0);
+    assert!(client.has_contributed(&admin, &group1, &1));
+
+    // Both rounds' contributions are escrowed again
+    assert_eq!(client.get_group_balance(&group1), 1_000_000);
+    assert_eq!(client.get_group_balance(&group2), 2_000_000);
+
+    // The full round pays out from its own escrow
+    let before = token_client.balance(&admin);
+    client.distribute_payout(&group2);
+    assert_eq!(token_client.balance(&admin), before + 2_000_000);
+    assert_eq!(client.get_group_balance(&group2), 0);
+
+    // and the open one can be refunded
+    let before = token_client.balance(&admin);
+    client.emergency_withdraw(&admin, &group1);
+    assert_eq!(token_client.balance(&admin), before + 1_000_000);
+}
+
+#[test]
+fn test_upgrade_requires_protocol_admin() {
+    let (env, _admin, client, _token) = setup_env();
+    let stranger = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[0; 32]);
+
+    assert_eq!(
+        client.try_upgrade(&stranger, &hash),
+        Err(Ok(ContractError::Unauthorized))
+    );
 }
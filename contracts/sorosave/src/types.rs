@@ -9,6 +9,27 @@ pub enum GroupStatus {
     Completed, // All rounds finished, all payouts distributed
     Disputed,  // A dispute has been raised, group is frozen
     Paused,    // Admin has paused the group
+    Cancelled, // Abandoned before starting, deposits refunded
+}
+
+/// How the payout order is decided when a group starts.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PayoutOrderStrategy {
+    JoinOrder, // Members are paid in the order they joined
+    Random,    // Join order shuffled with the ledger PRNG
+    Explicit,  // Order set by the group admin before starting
+    Bid,       // Highest position bid is paid first
+    Auction,   // Each round goes to the lowest bidder among unpaid members
+}
+
+/// Who may join a forming group. Allowlisted addresses can always join.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum GroupVisibility {
+    Public,     // Anyone can join
+    Allowlist,  // Only addresses the admin has approved
+    InviteCode, // Anyone holding the invite code
 }
 
 /// Core savings group configuration and state.
@@ -22,12 +43,20 @@ pub struct SavingsGroup {
     pub contribution_amount: i128,
     pub cycle_length: u64,
     pub max_members: u32,
+    pub collateral: i128,
     pub members: Vec<Address>,
+    pub payout_strategy: PayoutOrderStrategy,
     pub payout_order: Vec<Address>,
     pub current_round: u32,
     pub total_rounds: u32,
     pub status: GroupStatus,
     pub created_at: u64,
+    pub auto_payout: bool,       // pay out as soon as the round is complete
+    pub transfer_approval: bool, // seat transfers need the group admin's sign-off
+    pub min_reputation: u32,     // reputation score required to join
+    pub visibility: GroupVisibility,
+    pub auto_start_at: u64, // 0 means no scheduled start
+    pub auto_start_when_full: bool,
 }
 
 /// Tracks contributions and payout status for a single round.
@@ -36,19 +65,84 @@ pub struct SavingsGroup {
 pub struct RoundInfo {
     pub round_number: u32,
     pub recipient: Address,
-    pub contributions: Map<Address, bool>,
+    pub contributions: Map<Address, i128>,
     pub total_contributed: i128,
     pub is_complete: bool,
     pub deadline: u64,
+    pub defaulters: Vec<Address>,
+    pub discount: i128,
+    pub asset_totals: Map<Address, i128>,
+    pub payments: Map<Address, i128>,
+}
+
+/// Resolution that members can vote for in a dispute.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisputeOutcome {
+    Dismiss,               // Unfreeze the group unchanged
+    RemoveMember(Address), // Remove a member from the group
+    DissolveGroup,         // Refund and wind up the group
+    ReplaceAdmin(Address), // Hand the admin role to another member
+}
+
+/// Whether a dispute is still being voted on, and how it ended.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Resolved(DisputeOutcome),
 }
 
-/// Dispute information for a group.
+/// Dispute information for a group. Disputes are numbered per group and kept
+/// after resolution as an audit trail.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Dispute {
+    pub id: u32,
     pub raised_by: Address,
     pub reason: String,
     pub raised_at: u64,
+    pub proposed: DisputeOutcome,
+    pub votes: Map<Address, DisputeOutcome>,
+    pub voting_ends: u64,
+    pub evidence: Vec<String>,
+    pub status: DisputeStatus,
+    pub resolved_at: u64,
+}
+
+/// Protocol fee rates in basis points.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeRates {
+    pub payout_bps: u32,
+    pub contribution_bps: u32,
+}
+
+/// Contributions a member has paid ahead for future rounds.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prepayment {
+    pub rounds: u32,
+    pub amount: i128, // escrowed in the member's contribution token
+}
+
+/// A member's payment history across all groups.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reputation {
+    pub rounds_on_time: u32,
+    pub late_payments: u32,
+    pub defaults: u32,
+    pub groups_completed: u32,
+    pub score: u32,
+}
+
+/// Protocol-wide fee configuration.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeConfig {
+    pub treasury: Address,
+    pub rates: FeeRates,
 }
 
 /// Storage keys for all contract data.
@@ -56,9 +150,38 @@ pub struct Dispute {
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
+    PendingAdmin,
+    SchemaVersion,
+    MigrationCursor,
+    FeeConfig,
+    TokenFee(Address),
+    KeeperFeeShare,
     GroupCounter,
     Group(u64),
+    PendingGroupAdmin(u64),
     Round(u64, u32),
     MemberGroups(Address),
-    Dispute(u64),
+    Reputation(Address),
+    Allowlist(u64),
+    InviteCode(u64),
+    JoinRequests(u64),
+    Dispute(u64, u32),
+    DisputeCounter(u64),
+    DefaultCount(u64, Address),
+    GroupBalance(u64),
+    AssetBalance(u64, Address),
+    AcceptedTokens(u64),
+    MemberToken(u64, Address),
+    PriceOracle(u64),
+    Prepayment(u64, Address),
+    KeeperPool(u64),
+    KeeperFunders(u64),
+    KeeperReward(u64),
+    ExitPenalty(u64),
+    ExitReserve(u64),
+    ExitClaims(u64),
+    Collateral(u64, Address),
+    PositionBids(u64),
+    BidPool(u64),
+    AuctionBids(u64, u32),
 }
@@ -0,0 +1,232 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map, String, Vec};
+
+use crate::admin::DISPUTE_VOTING_PERIOD;
+use crate::errors::ContractError;
+use crate::storage;
+use crate::types::{
+    Dispute, DisputeOutcome, DisputeStatus, GroupStatus, GroupVisibility, PayoutOrderStrategy,
+    RoundInfo, SavingsGroup,
+};
+
+/// Storage layout version written by this build of the contract.
+pub const SCHEMA_VERSION: u32 = 1;
+
+// --- Legacy layouts (schema version 0) ---
+
+#[contracttype(export = false)]
+#[derive(Clone)]
+pub enum LegacyDataKey {
+    Group(u64),
+    Round(u64, u32),
+    Dispute(u64),
+}
+
+#[contracttype(export = false)]
+#[derive(Clone)]
+pub struct SavingsGroupV0 {
+    pub id: u64,
+    pub name: String,
+    pub admin: Address,
+    pub token: Address,
+    pub contribution_amount: i128,
+    pub cycle_length: u64,
+    pub max_members: u32,
+    pub members: Vec<Address>,
+    pub payout_order: Vec<Address>,
+    pub current_round: u32,
+    pub total_rounds: u32,
+    pub status: GroupStatus,
+    pub created_at: u64,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone)]
+pub struct RoundInfoV0 {
+    pub round_number: u32,
+    pub recipient: Address,
+    pub contributions: Map<Address, bool>,
+    pub total_contributed: i128,
+    pub is_complete: bool,
+    pub deadline: u64,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone)]
+pub struct DisputeV0 {
+    pub raised_by: Address,
+    pub reason: String,
+    pub raised_at: u64,
+}
+
+/// Swap the contract code for a previously uploaded WASM. Storage is left as is;
+/// call `migrate` afterwards if the new code expects a newer layout.
+pub fn upgrade(env: &Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    env.events()
+        .publish((crate::symbol_short!("upgraded"),), new_wasm_hash);
+
+    Ok(())
+}
+
+/// Rewrite the stored records of groups `start_id..=end_id` from older layouts
+/// into the current one. Batches must follow on from each other, starting at
+/// group 1, so no group is rewritten twice. The schema version only moves once
+/// the last group is done; until then the old version is returned.
+pub fn migrate(
+    env: &Env,
+    admin: Address,
+    start_id: u64,
+    end_id: u64,
+) -> Result<u32, ContractError> {
+    admin.require_auth();
+
+    if admin != storage::get_admin(env) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let from_version = storage::get_schema_version(env);
+    if from_version >= SCHEMA_VERSION {
+        return Err(ContractError::AlreadyMigrated);
+    }
+
+    if start_id != storage::get_migration_cursor(env) || end_id < start_id {
+        return Err(ContractError::InvalidMigrationRange);
+    }
+
+    let last_id = end_id.min(storage::get_group_counter(env));
+    if from_version == 0 {
+        for group_id in start_id..=last_id {
+            migrate_v0(env, group_id);
+        }
+    }
+
+    if last_id < storage::get_group_counter(env) {
+        storage::set_migration_cursor(env, last_id + 1);
+        return Ok(from_version);
+    }
+
+    storage::remove_migration_cursor(env);
+    storage::set_schema_version(env, SCHEMA_VERSION);
+
+    env.events().publish(
+        (crate::symbol_short!("migrated"),),
+        (from_version, SCHEMA_VERSION),
+    );
+
+    Ok(SCHEMA_VERSION)
+}
+
+pub fn get_schema_version(env: &Env) -> u32 {
+    storage::get_schema_version(env)
+}
+
+/// Version 0 predates collateral, payout strategies, defaults, auctions and
+/// numbered disputes. Legacy records are read straight from storage since the
+/// current helpers only understand the new layout.
+fn migrate_v0(env: &Env, group_id: u64) {
+    let key = LegacyDataKey::Group(group_id);
+    let Some(old): Option<SavingsGroupV0> = env.storage().persistent().get(&key) else {
+        return;
+    };
+
+    // Version 0 only ever held the open round's contributions
+    let mut escrow = 0;
+    for round in 1..=old.current_round {
+        let round_key = LegacyDataKey::Round(group_id, round);
+        if let Some(old_round) = env.storage().persistent().get::<_, RoundInfoV0>(&round_key) {
+            // Version 0 groups only ever held their single token
+            let mut asset_totals = Map::new(env);
+            let mut payments = Map::new(env);
+            if old_round.total_contributed > 0 {
+                asset_totals.set(old.token.clone(), old_round.total_contributed);
+            }
+            let mut contributions = Map::new(env);
+            for member in old_round.contributions.keys() {
+                contributions.set(member.clone(), old.contribution_amount);
+                payments.set(member, old.contribution_amount);
+            }
+            // A completed round keeps its pot until it is paid out
+            if round == old.current_round && old.status != GroupStatus::Completed {
+                escrow = old_round.total_contributed;
+            }
+            storage::set_round(
+                env,
+                group_id,
+                &RoundInfo {
+                    round_number: old_round.round_number,
+                    recipient: old_round.recipient,
+                    contributions,
+                    total_contributed: old_round.total_contributed,
+                    is_complete: old_round.is_complete,
+                    deadline: old_round.deadline,
+                    defaulters: Vec::new(env),
+                    discount: 0,
+                    asset_totals,
+                    payments,
+                },
+            );
+        }
+    }
+
+    // Only an unresolved dispute could still be stored
+    let dispute_key = LegacyDataKey::Dispute(group_id);
+    if let Some(old_dispute) = env.storage().persistent().get::<_, DisputeV0>(&dispute_key) {
+        env.storage().persistent().remove(&dispute_key);
+        storage::set_dispute(
+            env,
+            group_id,
+            &Dispute {
+                id: 1,
+                raised_by: old_dispute.raised_by,
+                reason: old_dispute.reason,
+                raised_at: old_dispute.raised_at,
+                proposed: DisputeOutcome::Dismiss,
+                votes: Map::new(env),
+                voting_ends: env.ledger().timestamp() + DISPUTE_VOTING_PERIOD,
+                evidence: Vec::new(env),
+                status: DisputeStatus::Open,
+                resolved_at: 0,
+            },
+        );
+        storage::set_dispute_counter(env, group_id, 1);
+    }
+
+    if escrow > 0 {
+        storage::set_group_balance(env, group_id, escrow);
+    }
+
+    storage::set_group(
+        env,
+        &SavingsGroup {
+            id: old.id,
+            name: old.name,
+            admin: old.admin,
+            token: old.token,
+            contribution_amount: old.contribution_amount,
+            cycle_length: old.cycle_length,
+            max_members: old.max_members,
+            collateral: 0,
+            members: old.members,
+            payout_strategy: PayoutOrderStrategy::JoinOrder,
+            payout_order: old.payout_order,
+            current_round: old.current_round,
+            total_rounds: old.total_rounds,
+            status: old.status,
+            created_at: old.created_at,
+            auto_payout: false,
+            transfer_approval: false,
+            min_reputation: 0,
+            visibility: GroupVisibility::Public,
+            auto_start_at: 0,
+            auto_start_when_full: false,
+        },
+    );
+}